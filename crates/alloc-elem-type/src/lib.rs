@@ -0,0 +1,85 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Types of vector elements that could be allocated by the exported `allocate` function.
+//! The enum is shared by the allocator and the `#[marine]` macro, so tags used by the allocator
+//! and types of vectors in the generated code can't drift apart.
+
+#![doc(html_root_url = "https://docs.rs/marine-alloc-elem-type/0.6.10")]
+#![deny(
+    dead_code,
+    nonstandard_style,
+    unused_imports,
+    unused_mut,
+    unused_variables,
+    unused_unsafe,
+    unreachable_patterns
+)]
+#![warn(rust_2018_idioms)]
+
+/// Types of vector elements that could be allocated by the exported `allocate` function.
+/// Discriminants are the type tags passed by a host as the `elem_ty` argument of `allocate`
+/// and `deallocate`, so they must not be changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AllocElemType {
+    Boolean = 0,
+    U8 = 1,
+    U16 = 2,
+    U32 = 3,
+    U64 = 4,
+    I8 = 5,
+    I16 = 6,
+    I32 = 7,
+    I64 = 8,
+    F32 = 9,
+    F64 = 10,
+}
+
+impl AllocElemType {
+    /// Returns an element type corresponding to the given type tag or None if it's unknown.
+    pub fn from_type_tag(type_tag: usize) -> Option<Self> {
+        let elem_ty = match type_tag {
+            0 => AllocElemType::Boolean,
+            1 => AllocElemType::U8,
+            2 => AllocElemType::U16,
+            3 => AllocElemType::U32,
+            4 => AllocElemType::U64,
+            5 => AllocElemType::I8,
+            6 => AllocElemType::I16,
+            7 => AllocElemType::I32,
+            8 => AllocElemType::I64,
+            9 => AllocElemType::F32,
+            10 => AllocElemType::F64,
+            _ => return None,
+        };
+
+        Some(elem_ty)
+    }
+
+    pub fn type_tag(self) -> usize {
+        self as usize
+    }
+
+    /// Returns a size of one element of this type in bytes.
+    pub fn size(self) -> usize {
+        match self {
+            AllocElemType::Boolean | AllocElemType::U8 | AllocElemType::I8 => 1,
+            AllocElemType::U16 | AllocElemType::I16 => 2,
+            AllocElemType::U32 | AllocElemType::I32 | AllocElemType::F32 => 4,
+            AllocElemType::U64 | AllocElemType::I64 | AllocElemType::F64 => 8,
+        }
+    }
+}
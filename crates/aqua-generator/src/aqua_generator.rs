@@ -0,0 +1,242 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::AResult;
+use crate::AquaGeneratorError;
+
+use marine_macro_impl::FnType;
+use marine_macro_impl::ParsedType;
+use marine_macro_impl::RecordType;
+use marine_macro_impl::SDKAst;
+use marine_module_interface::collect_records;
+use marine_module_interface::find_record;
+use marine_module_interface::named_fields;
+use marine_module_interface::output_type;
+use marine_module_interface::primitive_type_name;
+use marine_module_interface::record_name;
+use marine_module_interface::Records;
+
+use std::collections::HashSet;
+use std::fmt::Write;
+
+const INDENT: &str = "  ";
+
+/// Generates Aqua declarations of records used by exported functions and a service with these
+/// functions. Functions are sorted by name and records are declared before their first use,
+/// so the output doesn't depend on the order of items in a module.
+pub fn generate_aqua(items: &[SDKAst], service_id: &str) -> AResult<String> {
+    let records = collect_records(items)?;
+
+    let mut functions = items
+        .iter()
+        .filter_map(|item| match item {
+            SDKAst::Function(function) => Some(function),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    functions.sort_by(|lhs, rhs| lhs.signature.name.cmp(&rhs.signature.name));
+
+    let mut ordered_records = OrderedRecords::new(&records);
+    for function in functions.iter() {
+        let signature = &function.signature;
+        let arg_types = signature.arguments.iter().map(|argument| &argument.ty);
+        for ty in arg_types.chain(signature.output_types.iter()) {
+            ordered_records.visit_type(ty)?;
+        }
+    }
+
+    let mut aqua = String::new();
+    for record in ordered_records.records {
+        write_record(&mut aqua, record, &records)?;
+        aqua.push('\n');
+    }
+    write_service(&mut aqua, &functions, service_id, &records)?;
+
+    Ok(aqua)
+}
+
+/// Orders records so that each record is preceded by records it refers to.
+struct OrderedRecords<'r, 'i> {
+    all_records: &'r Records<'i>,
+    visited: HashSet<&'i str>,
+    records: Vec<&'i RecordType>,
+}
+
+impl<'r, 'i> OrderedRecords<'r, 'i> {
+    fn new(all_records: &'r Records<'i>) -> Self {
+        Self {
+            all_records,
+            visited: HashSet::new(),
+            records: Vec::new(),
+        }
+    }
+
+    fn visit_type(&mut self, ty: &ParsedType) -> AResult<()> {
+        match ty {
+            ParsedType::Vector(ty, _) => self.visit_type(ty),
+            ParsedType::Record(name, _) => self.visit_record(name),
+            _ => Ok(()),
+        }
+    }
+
+    fn visit_record(&mut self, name: &str) -> AResult<()> {
+        let record = find_record(name, self.all_records)?;
+        if !self.visited.insert(record.name.as_str()) {
+            return Ok(());
+        }
+
+        for field in named_fields(record)? {
+            self.visit_type(&field.ty)?;
+        }
+        self.records.push(record);
+
+        Ok(())
+    }
+}
+
+fn write_record(aqua: &mut String, record: &RecordType, records: &Records<'_>) -> AResult<()> {
+    let name = record_name(record);
+    check_type_name(name)?;
+
+    write_docs(aqua, &record.docs, "");
+    writeln!(aqua, "data {}:", name).unwrap();
+    for field in named_fields(record)? {
+        // only named fields are possible here
+        let field_name = field.name.as_deref().unwrap_or_default();
+        check_name("field", field_name)?;
+
+        write_docs(aqua, &field.docs, INDENT);
+        let ty = aqua_type(&field.ty, records)?;
+        writeln!(aqua, "{}{}: {}", INDENT, field_name, ty).unwrap();
+    }
+
+    Ok(())
+}
+
+fn write_service(
+    aqua: &mut String,
+    functions: &[&FnType],
+    service_id: &str,
+    records: &Records<'_>,
+) -> AResult<()> {
+    let service_name = service_name(service_id)?;
+    writeln!(aqua, "service {}(\"{}\"):", service_name, service_id).unwrap();
+
+    for function in functions {
+        let signature = &function.signature;
+        check_name("function", &signature.name)?;
+
+        let arguments = signature
+            .arguments
+            .iter()
+            .map(|argument| {
+                check_name("argument", &argument.name)?;
+                let ty = aqua_type(&argument.ty, records)?;
+                Ok(format!("{}: {}", argument.name, ty))
+            })
+            .collect::<AResult<Vec<_>>>()?;
+
+        let output = match output_type(signature)? {
+            Some(ty) => format!(" -> {}", aqua_type(ty, records)?),
+            None => String::new(),
+        };
+
+        write_docs(aqua, &signature.docs, INDENT);
+        writeln!(
+            aqua,
+            "{}{}({}){}",
+            INDENT,
+            signature.name,
+            arguments.join(", "),
+            output
+        )
+        .unwrap();
+    }
+
+    Ok(())
+}
+
+fn write_docs(aqua: &mut String, docs: &Option<String>, indent: &str) {
+    let docs = match docs {
+        Some(docs) => docs,
+        None => return,
+    };
+
+    for line in docs.split('\n') {
+        if line.is_empty() {
+            writeln!(aqua, "{}--", indent).unwrap();
+        } else {
+            writeln!(aqua, "{}-- {}", indent, line).unwrap();
+        }
+    }
+}
+
+fn aqua_type(ty: &ParsedType, records: &Records<'_>) -> AResult<String> {
+    let ty = match ty {
+        ParsedType::Vector(ty, _) => format!("[]{}", aqua_type(ty, records)?),
+        ParsedType::Record(name, _) => record_name(find_record(name, records)?).to_string(),
+        // other types are primitive
+        ty => primitive_type_name(ty).unwrap_or_default().to_string(),
+    };
+
+    Ok(ty)
+}
+
+/// Converts a service id like `ipfs-adapter` to a service name like `IpfsAdapter`.
+fn service_name(service_id: &str) -> AResult<String> {
+    let name = service_id
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            // parts are non-empty, so the first char is always present
+            let first = chars.next().unwrap_or_default().to_ascii_uppercase();
+            std::iter::once(first).chain(chars).collect::<String>()
+        })
+        .collect::<String>();
+
+    check_type_name(&name)?;
+    Ok(name)
+}
+
+/// Checks names of functions, arguments and fields, they should start with a lowercase letter.
+fn check_name(kind: &'static str, name: &str) -> AResult<()> {
+    check_identifier(kind, name, |c| c.is_ascii_lowercase() || c == '_')
+}
+
+/// Checks names of types, they should start with an uppercase letter.
+fn check_type_name(name: &str) -> AResult<()> {
+    check_identifier("type", name, |c| c.is_ascii_uppercase())
+}
+
+fn check_identifier(
+    kind: &'static str,
+    name: &str,
+    is_valid_first_char: impl Fn(char) -> bool,
+) -> AResult<()> {
+    let mut chars = name.chars();
+    let is_valid = matches!(chars.next(), Some(c) if is_valid_first_char(c))
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+    if is_valid {
+        Ok(())
+    } else {
+        Err(AquaGeneratorError::InvalidAquaName {
+            kind,
+            name: name.to_string(),
+        })
+    }
+}
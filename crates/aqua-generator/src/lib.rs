@@ -0,0 +1,46 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#![doc(html_root_url = "https://docs.rs/marine-aqua-generator/0.1.0")]
+#![deny(
+    dead_code,
+    nonstandard_style,
+    unused_imports,
+    unused_mut,
+    unused_variables,
+    unused_unsafe,
+    unreachable_patterns
+)]
+#![warn(rust_2018_idioms)]
+
+//! Generates Aqua `data` and `service` declarations from interfaces that the `#[marine]` macro
+//! embeds into custom sections of a compiled module:
+//!```ignore
+//! let items = marine_module_interface::extract_sdk_ast("greeting.wasm")?;
+//! let aqua = marine_aqua_generator::generate_aqua(&items, "greeting")?;
+//!```
+
+mod aqua_generator;
+mod errors;
+
+pub use aqua_generator::generate_aqua;
+pub use errors::AquaGeneratorError;
+
+pub use marine_module_interface::extract_sdk_ast;
+pub use marine_module_interface::extract_sdk_ast_from_bytes;
+pub use marine_module_interface::SDKAst;
+
+pub(crate) type AResult<T> = std::result::Result<T, AquaGeneratorError>;
@@ -0,0 +1,107 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use marine_aqua_generator::extract_sdk_ast;
+use marine_aqua_generator::generate_aqua;
+
+use std::path::PathBuf;
+
+const USAGE: &str = "\
+Generates Aqua definitions of a Marine module
+
+USAGE:
+    marine-aqua-generator <module.wasm> [--service-id <id>] [--output <file.aqua>]
+
+OPTIONS:
+    --service-id <id>       id of the service, the module file name is used by default
+    --output <file.aqua>    file to write definitions to, stdout is used by default";
+
+struct Args {
+    wasm_path: PathBuf,
+    service_id: Option<String>,
+    output_path: Option<PathBuf>,
+}
+
+fn main() {
+    if std::env::args().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{}", USAGE);
+        return;
+    }
+
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    if let Err(message) = run(args) {
+        eprintln!("error: {}", message);
+        std::process::exit(1);
+    }
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let service_id = match args.service_id {
+        Some(service_id) => service_id,
+        None => args
+            .wasm_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .ok_or_else(|| format!("{} isn't a path to a file", args.wasm_path.display()))?,
+    };
+
+    let items = extract_sdk_ast(&args.wasm_path).map_err(|e| e.to_string())?;
+    let aqua = generate_aqua(&items, &service_id).map_err(|e| e.to_string())?;
+
+    match args.output_path {
+        Some(output_path) => std::fs::write(&output_path, aqua)
+            .map_err(|e| format!("can't write {}: {}", output_path.display(), e)),
+        None => {
+            print!("{}", aqua);
+            Ok(())
+        }
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut wasm_path = None;
+    let mut service_id = None;
+    let mut output_path = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--service-id" => service_id = Some(option_value(&arg, args.next())?),
+            "--output" => output_path = Some(PathBuf::from(option_value(&arg, args.next())?)),
+            _ if arg.starts_with('-') => return Err(format!("unknown option {}", arg)),
+            _ if wasm_path.is_none() => wasm_path = Some(PathBuf::from(arg)),
+            _ => return Err(format!("unexpected argument {}", arg)),
+        }
+    }
+
+    let wasm_path = wasm_path.ok_or_else(|| String::from("path to a module isn't specified"))?;
+
+    Ok(Args {
+        wasm_path,
+        service_id,
+        output_path,
+    })
+}
+
+fn option_value(option: &str, value: Option<String>) -> Result<String, String> {
+    value.ok_or_else(|| format!("{} requires a value", option))
+}
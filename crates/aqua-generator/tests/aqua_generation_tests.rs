@@ -0,0 +1,134 @@
+use marine_aqua_generator::extract_sdk_ast_from_bytes;
+use marine_aqua_generator::generate_aqua;
+use marine_aqua_generator::AquaGeneratorError;
+use marine_macro_impl::encode_sdk_ast;
+use marine_macro_impl::SectionEncoding;
+use marine_macro_impl::GENERATED_SECTION_PREFIX;
+use marine_module_interface::ModuleInterfaceError;
+
+const GET_USERS: &str = r#"{"ast_type":"Function","signature":{"name":"get_users","arguments":[{"name":"names","ty":{"Vector":[{"Utf8String":"ByValue"},"ByValue"]}}],"output_types":[{"Vector":[{"Record":["User","ByValue"]},"ByValue"]}],"docs":"Returns users by their names."}}"#;
+const ADD_USER: &str = r#"{"ast_type":"Function","signature":{"name":"add_user","arguments":[{"name":"user","ty":{"Record":["User","ByRef"]}},{"name":"force","ty":{"Boolean":"ByValue"}}],"output_types":[]}}"#;
+const USER: &str = r#"{"ast_type":"Record","name":"User","fields":{"Named":[{"name":"id","ty":{"U64":"ByValue"},"docs":"Unique id."},{"name":"addresses","ty":{"Vector":[{"Record":["Address","ByValue"]},"ByValue"]}}]}}"#;
+const ADDRESS: &str = r#"{"ast_type":"Record","name":"Address","export_name":"PostalAddress","fields":{"Named":[{"name":"city","ty":{"Utf8String":"ByValue"}},{"name":"tags","ty":{"Vector":[{"Vector":[{"U8":"ByValue"},"ByValue"]},"ByValue"]}}]}}"#;
+const UNUSED: &str = r#"{"ast_type":"Record","name":"Unused","fields":{"Named":[{"name":"flag","ty":{"Boolean":"ByValue"}}]}}"#;
+
+const EXPECTED_AQUA: &str = "\
+data PostalAddress:
+  city: string
+  tags: [][]u8
+
+data User:
+  -- Unique id.
+  id: u64
+  addresses: []PostalAddress
+
+service UserStore(\"user-store\"):
+  add_user(user: User, force: bool)
+  -- Returns users by their names.
+  get_users(names: []string) -> []User
+";
+
+/// Creates a module with the given custom sections.
+fn module_with_sections(sections: &[(String, Vec<u8>)]) -> Vec<u8> {
+    let mut module = b"\0asm\x01\0\0\0".to_vec();
+    for (name, data) in sections {
+        let mut payload = leb128(name.len());
+        payload.extend(name.as_bytes());
+        payload.extend(data);
+
+        module.push(0);
+        module.extend(leb128(payload.len()));
+        module.extend(payload);
+    }
+
+    module
+}
+
+fn leb128(mut value: usize) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            bytes.push(byte);
+            return bytes;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+fn section(name: &str, items: &[&str], encoding: Option<SectionEncoding>) -> (String, Vec<u8>) {
+    let data = items
+        .iter()
+        .flat_map(|item| match encoding {
+            Some(encoding) => {
+                let item = serde_json::from_str(item).expect("item should be valid");
+                encode_sdk_ast(&item, encoding)
+            }
+            // layout of sections embedded by previous versions of the sdk
+            None => item.as_bytes().to_vec(),
+        })
+        .collect();
+
+    (format!("{}{}", GENERATED_SECTION_PREFIX, name), data)
+}
+
+#[test]
+fn aqua_generated_from_module() {
+    let module = module_with_sections(&[
+        section("get_users", &[GET_USERS], Some(SectionEncoding::Json)),
+        section("User", &[USER], Some(SectionEncoding::Cbor)),
+        section("add_user", &[ADD_USER], None),
+        section("Address", &[ADDRESS, UNUSED], Some(SectionEncoding::Json)),
+        ("unrelated".to_string(), b"data".to_vec()),
+    ]);
+
+    let items = extract_sdk_ast_from_bytes(&module).expect("items should be extracted");
+    let aqua = generate_aqua(&items, "user-store").expect("aqua should be generated");
+
+    assert_eq!(aqua, EXPECTED_AQUA);
+}
+
+#[test]
+fn aqua_independent_of_items_order() {
+    let mut items = [GET_USERS, USER, ADD_USER, ADDRESS]
+        .iter()
+        .map(|item| serde_json::from_str(item).expect("item should be valid"))
+        .collect::<Vec<_>>();
+
+    let aqua = generate_aqua(&items, "user-store").expect("aqua should be generated");
+    items.reverse();
+    let reversed_aqua = generate_aqua(&items, "user-store").expect("aqua should be generated");
+
+    assert_eq!(aqua, reversed_aqua);
+}
+
+#[test]
+fn unknown_record_rejected() {
+    let items = vec![serde_json::from_str(GET_USERS).expect("item should be valid")];
+
+    match generate_aqua(&items, "user-store") {
+        Err(AquaGeneratorError::InterfaceError(ModuleInterfaceError::UnknownRecord(name))) => {
+            assert_eq!(name, "User")
+        }
+        result => panic!("expected UnknownRecord, got {:?}", result),
+    }
+}
+
+#[test]
+fn invalid_aqua_name_rejected() {
+    let renamed = ADD_USER.replace("add_user", "add-user");
+    let items = vec![
+        serde_json::from_str(&renamed).expect("item should be valid"),
+        serde_json::from_str(USER).expect("item should be valid"),
+        serde_json::from_str(ADDRESS).expect("item should be valid"),
+    ];
+
+    match generate_aqua(&items, "user-store") {
+        Err(AquaGeneratorError::InvalidAquaName { kind, name }) => {
+            assert_eq!(kind, "function");
+            assert_eq!(name, "add-user");
+        }
+        result => panic!("expected InvalidAquaName, got {:?}", result),
+    }
+}
@@ -0,0 +1,305 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::CResult;
+use crate::InterfaceChange;
+
+use marine_macro_impl::FnSignature;
+use marine_macro_impl::ParsedType;
+use marine_macro_impl::RecordField;
+use marine_macro_impl::RecordFields;
+use marine_macro_impl::RecordType;
+use marine_macro_impl::SDKAst;
+use marine_module_interface::collect_records;
+use marine_module_interface::find_record;
+use marine_module_interface::primitive_type_name;
+use marine_module_interface::record_name;
+use marine_module_interface::Records;
+
+use std::collections::HashMap;
+
+/// Compares exported functions of two module builds and records they use. Arguments and fields
+/// are passed by position, so renaming an argument is compatible, while records are passed
+/// to callers with field names, so renaming a field is breaking. Changes are sorted by
+/// function names.
+pub fn check_compatibility(old: &[SDKAst], new: &[SDKAst]) -> CResult<Vec<InterfaceChange>> {
+    let old = Interface::new(old)?;
+    let new = Interface::new(new)?;
+    let mut checker = Checker {
+        old: &old,
+        new: &new,
+        compared_records: HashMap::new(),
+        changes: Vec::new(),
+    };
+
+    let mut function_names = old
+        .functions
+        .keys()
+        .chain(new.functions.keys())
+        .copied()
+        .collect::<Vec<_>>();
+    function_names.sort_unstable();
+    function_names.dedup();
+
+    for name in function_names {
+        match (old.functions.get(name), new.functions.get(name)) {
+            (Some(old_fn), Some(new_fn)) => checker.compare_functions(old_fn, new_fn)?,
+            (Some(_), None) => checker.breaking(format!("function {} removed", name)),
+            (None, Some(_)) => checker.compatible(format!("function {} added", name)),
+            (None, None) => {}
+        }
+    }
+
+    Ok(checker.changes)
+}
+
+struct Interface<'i> {
+    functions: HashMap<&'i str, &'i FnSignature>,
+    records: Records<'i>,
+}
+
+impl<'i> Interface<'i> {
+    fn new(items: &'i [SDKAst]) -> CResult<Self> {
+        let functions = items
+            .iter()
+            .filter_map(|item| match item {
+                SDKAst::Function(function) => {
+                    let signature = &function.signature;
+                    Some((signature.name.as_str(), signature))
+                }
+                // imports don't affect callers of the module
+                _ => None,
+            })
+            .collect();
+        let records = collect_records(items)?;
+
+        Ok(Self { functions, records })
+    }
+
+    fn record(&self, name: &str) -> CResult<&'i RecordType> {
+        find_record(name, &self.records).map_err(Into::into)
+    }
+
+    fn type_name(&self, ty: &ParsedType) -> String {
+        match ty {
+            ParsedType::Vector(ty, _) => format!("[]{}", self.type_name(ty)),
+            ParsedType::Record(name, _) => match self.records.get(name.as_str()) {
+                Some(record) => record_name(record).to_string(),
+                None => name.clone(),
+            },
+            // other types are primitive
+            ty => primitive_type_name(ty).unwrap_or_default().to_string(),
+        }
+    }
+}
+
+struct Checker<'c, 'i> {
+    old: &'c Interface<'i>,
+    new: &'c Interface<'i>,
+    /// Results of comparing records by old and new Rust names, so that changes of a record
+    /// used in several places are reported once.
+    compared_records: HashMap<(&'i str, &'i str), bool>,
+    changes: Vec<InterfaceChange>,
+}
+
+impl<'c, 'i> Checker<'c, 'i> {
+    fn compare_functions(&mut self, old: &'i FnSignature, new: &'i FnSignature) -> CResult<()> {
+        let name = &old.name;
+
+        if old.arguments.len() != new.arguments.len() {
+            self.breaking(format!(
+                "function {}: number of arguments changed from {} to {}",
+                name,
+                old.arguments.len(),
+                new.arguments.len()
+            ));
+        } else {
+            for (old_arg, new_arg) in old.arguments.iter().zip(new.arguments.iter()) {
+                if old_arg.name != new_arg.name {
+                    self.compatible(format!(
+                        "function {}: argument {} renamed to {}",
+                        name, old_arg.name, new_arg.name
+                    ));
+                }
+
+                let subject = format!("function {}: type of argument {}", name, new_arg.name);
+                let _ = self.compare_types(&old_arg.ty, &new_arg.ty, subject)?;
+            }
+        }
+
+        match (old.output_types.as_slice(), new.output_types.as_slice()) {
+            ([old_ty], [new_ty]) => {
+                let subject = format!("function {}: return type", name);
+                let _ = self.compare_types(old_ty, new_ty, subject)?;
+            }
+            (old_types, new_types) if old_types.len() != new_types.len() => {
+                self.breaking(format!(
+                    "function {}: return type changed from {} to {}",
+                    name,
+                    self.output_name(self.old, old_types),
+                    self.output_name(self.new, new_types),
+                ));
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if types are compatible, a change is reported only if it isn't caused
+    /// by changes of record fields, they are reported by `compare_records`.
+    fn compare_types(
+        &mut self,
+        old: &'i ParsedType,
+        new: &'i ParsedType,
+        subject: String,
+    ) -> CResult<bool> {
+        if self.is_compatible(old, new)? {
+            return Ok(true);
+        }
+
+        let old_name = self.old.type_name(old);
+        let new_name = self.new.type_name(new);
+        if old_name != new_name {
+            self.breaking(format!(
+                "{} changed from {} to {}",
+                subject, old_name, new_name
+            ));
+        }
+
+        Ok(false)
+    }
+
+    fn is_compatible(&mut self, old: &'i ParsedType, new: &'i ParsedType) -> CResult<bool> {
+        use ParsedType::*;
+
+        let is_compatible = match (old, new) {
+            (Vector(old, _), Vector(new, _)) => self.is_compatible(old, new)?,
+            (Record(old, _), Record(new, _)) => self.compare_records(old, new)?,
+            (Utf8Str(_), Utf8String(_)) | (Utf8String(_), Utf8Str(_)) => true,
+            // passing style doesn't matter for callers
+            _ => std::mem::discriminant(old) == std::mem::discriminant(new),
+        };
+
+        Ok(is_compatible)
+    }
+
+    fn compare_records(&mut self, old_name: &'i str, new_name: &'i str) -> CResult<bool> {
+        if let Some(is_compatible) = self.compared_records.get(&(old_name, new_name)) {
+            return Ok(*is_compatible);
+        }
+        // records can't be recursive, but it prevents an infinite loop on a malformed module
+        self.compared_records.insert((old_name, new_name), true);
+
+        let old = self.old.record(old_name)?;
+        let new = self.new.record(new_name)?;
+        let name = record_name(new);
+
+        if record_name(old) != name {
+            self.compatible(format!("record {} renamed to {}", record_name(old), name));
+        }
+
+        let is_compatible = match (&old.fields, &new.fields) {
+            (RecordFields::Named(old_fields), RecordFields::Named(new_fields)) => {
+                self.compare_fields(name, old_fields, new_fields)?
+            }
+            (old_fields, new_fields) if old_fields == new_fields => true,
+            _ => {
+                self.breaking(format!("record {}: kind of fields changed", name));
+                false
+            }
+        };
+
+        self.compared_records
+            .insert((old_name, new_name), is_compatible);
+        Ok(is_compatible)
+    }
+
+    fn compare_fields(
+        &mut self,
+        record_name: &str,
+        old_fields: &'i [RecordField],
+        new_fields: &'i [RecordField],
+    ) -> CResult<bool> {
+        let mut is_compatible = old_fields.len() == new_fields.len();
+
+        for position in 0..old_fields.len().max(new_fields.len()) {
+            let (old_field, new_field) = match (old_fields.get(position), new_fields.get(position))
+            {
+                (Some(old_field), Some(new_field)) => (old_field, new_field),
+                (Some(old_field), None) => {
+                    self.breaking(format!(
+                        "record {}: field {} removed",
+                        record_name,
+                        field_name(old_field)
+                    ));
+                    continue;
+                }
+                (None, Some(new_field)) => {
+                    self.breaking(format!(
+                        "record {}: field {} added",
+                        record_name,
+                        field_name(new_field)
+                    ));
+                    continue;
+                }
+                (None, None) => continue,
+            };
+
+            if old_field.name != new_field.name {
+                is_compatible = false;
+                self.breaking(format!(
+                    "record {}: field {} renamed to {}",
+                    record_name,
+                    field_name(old_field),
+                    field_name(new_field)
+                ));
+            }
+
+            let subject = format!(
+                "record {}: type of field {}",
+                record_name,
+                field_name(new_field)
+            );
+            is_compatible &= self.compare_types(&old_field.ty, &new_field.ty, subject)?;
+        }
+
+        Ok(is_compatible)
+    }
+
+    fn output_name(&self, interface: &Interface<'i>, output_types: &[ParsedType]) -> String {
+        match output_types {
+            [] => "()".to_string(),
+            types => types
+                .iter()
+                .map(|ty| interface.type_name(ty))
+                .collect::<Vec<_>>()
+                .join(", "),
+        }
+    }
+
+    fn compatible(&mut self, description: String) {
+        self.changes.push(InterfaceChange::compatible(description));
+    }
+
+    fn breaking(&mut self, description: String) {
+        self.changes.push(InterfaceChange::breaking(description));
+    }
+}
+
+fn field_name(field: &RecordField) -> &str {
+    field.name.as_deref().unwrap_or_default()
+}
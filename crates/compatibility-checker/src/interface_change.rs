@@ -0,0 +1,67 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// Existing callers of the module aren't affected by the change.
+    Compatible,
+    /// Existing callers of the module could fail after the change.
+    Breaking,
+}
+
+/// A change of the module interface between two builds.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InterfaceChange {
+    pub kind: ChangeKind,
+    pub description: String,
+}
+
+impl InterfaceChange {
+    pub(crate) fn compatible(description: String) -> Self {
+        Self {
+            kind: ChangeKind::Compatible,
+            description,
+        }
+    }
+
+    pub(crate) fn breaking(description: String) -> Self {
+        Self {
+            kind: ChangeKind::Breaking,
+            description,
+        }
+    }
+
+    pub fn is_breaking(&self) -> bool {
+        self.kind == ChangeKind::Breaking
+    }
+}
+
+impl fmt::Display for ChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Compatible => write!(f, "compatible"),
+            Self::Breaking => write!(f, "breaking"),
+        }
+    }
+}
+
+impl fmt::Display for InterfaceChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.kind, self.description)
+    }
+}
@@ -0,0 +1,46 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#![doc(html_root_url = "https://docs.rs/marine-compatibility-checker/0.1.0")]
+#![deny(
+    dead_code,
+    nonstandard_style,
+    unused_imports,
+    unused_mut,
+    unused_variables,
+    unused_unsafe,
+    unreachable_patterns
+)]
+#![warn(rust_2018_idioms)]
+
+//! Compares interfaces embedded by the `#[marine]` macro into two builds of a module and
+//! classifies each change as compatible or breaking for callers of the module:
+//!```ignore
+//! let old = marine_module_interface::extract_sdk_ast("old/greeting.wasm")?;
+//! let new = marine_module_interface::extract_sdk_ast("new/greeting.wasm")?;
+//! let changes = marine_compatibility_checker::check_compatibility(&old, &new)?;
+//!```
+
+mod checker;
+mod errors;
+mod interface_change;
+
+pub use checker::check_compatibility;
+pub use errors::CompatibilityCheckerError;
+pub use interface_change::ChangeKind;
+pub use interface_change::InterfaceChange;
+
+pub(crate) type CResult<T> = std::result::Result<T, CompatibilityCheckerError>;
@@ -0,0 +1,72 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use marine_compatibility_checker::check_compatibility;
+use marine_compatibility_checker::InterfaceChange;
+use marine_module_interface::extract_sdk_ast;
+
+const USAGE: &str = "\
+Checks whether a new build of a Marine module is compatible with an old one
+
+USAGE:
+    marine-compatibility-checker <old.wasm> <new.wasm>
+
+Exits with 1 if there are breaking changes and with 2 if modules can't be checked.";
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let (old_path, new_path) = match args.as_slice() {
+        [help] if help == "-h" || help == "--help" => {
+            println!("{}", USAGE);
+            return;
+        }
+        [old_path, new_path] => (old_path, new_path),
+        _ => {
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    let changes = match run(old_path, new_path) {
+        Ok(changes) => changes,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            std::process::exit(2);
+        }
+    };
+
+    for change in changes.iter() {
+        println!("{}", change);
+    }
+
+    let breaking_changes_count = changes.iter().filter(|change| change.is_breaking()).count();
+    println!(
+        "{} breaking, {} compatible changes",
+        breaking_changes_count,
+        changes.len() - breaking_changes_count
+    );
+
+    if breaking_changes_count != 0 {
+        std::process::exit(1);
+    }
+}
+
+fn run(old_path: &str, new_path: &str) -> Result<Vec<InterfaceChange>, String> {
+    let old = extract_sdk_ast(old_path).map_err(|e| format!("{}: {}", old_path, e))?;
+    let new = extract_sdk_ast(new_path).map_err(|e| format!("{}: {}", new_path, e))?;
+
+    check_compatibility(&old, &new).map_err(|e| e.to_string())
+}
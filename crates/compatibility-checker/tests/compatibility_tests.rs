@@ -0,0 +1,145 @@
+use marine_compatibility_checker::check_compatibility;
+use marine_compatibility_checker::ChangeKind;
+use marine_compatibility_checker::CompatibilityCheckerError;
+use marine_macro_impl::SDKAst;
+use marine_module_interface::ModuleInterfaceError;
+
+const GET_USER: &str = r#"{"ast_type":"Function","signature":{"name":"get_user","arguments":[{"name":"id","ty":{"U64":"ByValue"}}],"output_types":[{"Record":["User","ByValue"]}]}}"#;
+const SET_USER: &str = r#"{"ast_type":"Function","signature":{"name":"set_user","arguments":[{"name":"user","ty":{"Record":["User","ByRef"]}},{"name":"name","ty":{"Utf8String":"ByValue"}}],"output_types":[]}}"#;
+const USER: &str = r#"{"ast_type":"Record","name":"User","fields":{"Named":[{"name":"id","ty":{"U64":"ByValue"}},{"name":"address","ty":{"Record":["Address","ByValue"]}}]}}"#;
+const ADDRESS: &str = r#"{"ast_type":"Record","name":"Address","fields":{"Named":[{"name":"city","ty":{"Utf8String":"ByValue"}}]}}"#;
+
+fn items(items: &[&str]) -> Vec<SDKAst> {
+    items
+        .iter()
+        .map(|item| serde_json::from_str(item).expect("item should be valid"))
+        .collect()
+}
+
+fn changes(old: &[&str], new: &[&str]) -> Vec<(ChangeKind, String)> {
+    check_compatibility(&items(old), &items(new))
+        .expect("interfaces should be compared")
+        .into_iter()
+        .map(|change| (change.kind, change.description))
+        .collect()
+}
+
+#[test]
+fn same_interfaces_compatible() {
+    let interface = [GET_USER, SET_USER, USER, ADDRESS];
+
+    assert_eq!(changes(&interface, &interface), vec![]);
+}
+
+#[test]
+fn docs_and_passing_style_ignored() {
+    let new_set_user = SET_USER
+        .replace(
+            r#"{"Record":["User","ByRef"]}"#,
+            r#"{"Record":["User","ByValue"]}"#,
+        )
+        .replace(r#"{"Utf8String":"ByValue"}"#, r#"{"Utf8Str":"ByRef"}"#)
+        .replace(
+            r#""output_types":[]"#,
+            r#""output_types":[],"docs":"Sets a user.""#,
+        );
+
+    assert_eq!(
+        changes(&[SET_USER, USER, ADDRESS], &[&new_set_user, USER, ADDRESS]),
+        vec![]
+    );
+}
+
+#[test]
+fn added_and_removed_functions_classified() {
+    assert_eq!(
+        changes(&[GET_USER, USER, ADDRESS], &[SET_USER, USER, ADDRESS]),
+        vec![
+            (
+                ChangeKind::Breaking,
+                "function get_user removed".to_string()
+            ),
+            (
+                ChangeKind::Compatible,
+                "function set_user added".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn signature_changes_classified() {
+    let new_set_user = SET_USER
+        .replace(r#""name":"name""#, r#""name":"user_name""#)
+        .replace(
+            r#""output_types":[]"#,
+            r#""output_types":[{"Boolean":"ByValue"}]"#,
+        );
+    let new_get_user = GET_USER.replace(r#"{"U64":"ByValue"}"#, r#"{"Utf8String":"ByValue"}"#);
+
+    assert_eq!(
+        changes(
+            &[GET_USER, SET_USER, USER, ADDRESS],
+            &[&new_get_user, &new_set_user, USER, ADDRESS]
+        ),
+        vec![
+            (
+                ChangeKind::Breaking,
+                "function get_user: type of argument id changed from u64 to string".to_string()
+            ),
+            (
+                ChangeKind::Compatible,
+                "function set_user: argument name renamed to user_name".to_string()
+            ),
+            (
+                ChangeKind::Breaking,
+                "function set_user: return type changed from () to bool".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn nested_record_changes_reported_once() {
+    let new_address = ADDRESS.replace(
+        r#"{"name":"city","ty":{"Utf8String":"ByValue"}}"#,
+        r#"{"name":"city","ty":{"Utf8String":"ByValue"}},{"name":"zip","ty":{"U32":"ByValue"}}"#,
+    );
+
+    assert_eq!(
+        changes(
+            &[GET_USER, SET_USER, USER, ADDRESS],
+            &[GET_USER, SET_USER, USER, &new_address]
+        ),
+        vec![(
+            ChangeKind::Breaking,
+            "record Address: field zip added".to_string()
+        )]
+    );
+}
+
+#[test]
+fn renamed_record_with_same_layout_compatible() {
+    let new_user = USER.replace(
+        r#""name":"User","#,
+        r#""name":"User","export_name":"Person","#,
+    );
+
+    assert_eq!(
+        changes(&[GET_USER, USER, ADDRESS], &[GET_USER, &new_user, ADDRESS]),
+        vec![(
+            ChangeKind::Compatible,
+            "record User renamed to Person".to_string()
+        )]
+    );
+}
+
+#[test]
+fn unknown_record_rejected() {
+    match check_compatibility(&items(&[GET_USER]), &items(&[GET_USER])) {
+        Err(CompatibilityCheckerError::InterfaceError(ModuleInterfaceError::UnknownRecord(
+            name,
+        ))) => assert_eq!(name, "User"),
+        result => panic!("expected UnknownRecord, got {:?}", result),
+    }
+}
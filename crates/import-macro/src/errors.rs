@@ -0,0 +1,40 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use marine_it_parser::ITParserError;
+
+use syn::Error as SynError;
+use thiserror::Error as ThisError;
+
+use std::path::PathBuf;
+
+#[derive(Debug, ThisError)]
+pub enum ImportGeneratorError {
+    #[error("Can't load the imported Wasm module: {0}")]
+    ITParserError(#[from] ITParserError),
+
+    #[error("record with {0} is absent in embedded IT section")]
+    AbsentRecord(u64),
+
+    #[error("{0}")]
+    SynError(#[from] SynError),
+
+    #[error("a Wasm file compiled with newer version of sdk that supports multi-value")]
+    ManyFnOutputsUnsupported,
+
+    #[error("{0} is invalid UTF8 path")]
+    InvalidUTF8Path(PathBuf),
+}
@@ -0,0 +1,210 @@
+/*
+ * Copyright 2021 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::errors::ImportGeneratorError;
+use crate::utils;
+use crate::TResult;
+
+use marine_it_parser::interface::it::IRecordType;
+use marine_it_parser::interface::it::IType;
+use marine_it_parser::interface::MFunctionSignature;
+use marine_it_parser::interface::MModuleInterface;
+use marine_it_parser::interface::MRecordTypes;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Records defined in the sdk, they are reused instead of generated, because each `#[marine]`
+/// record exports a symbol depending on its name.
+const SDK_RECORDS: &[&str] = &[
+    "CallParameters",
+    "SecurityTetraplet",
+    "MountedBinaryResult",
+    "MountedBinaryStringResult",
+];
+
+/// Generates records used by functions of the module and an extern block importing them.
+/// F.e. for the greeting module exporting `fn greeting(user: User) -> String`:
+///```ignore
+/// const _: &[u8] = include_bytes!("/path/to/greeting.wasm");
+///
+/// #[marine_rs_sdk::marine]
+/// #[derive(Clone, Debug, Default)]
+/// pub struct User {
+///     pub name: String,
+/// }
+///
+/// #[marine_rs_sdk::marine]
+/// #[link(wasm_import_module = "greeting")]
+/// extern "C" {
+///     pub fn greeting(user: User) -> String;
+/// }
+///```
+pub(crate) fn generate_import(
+    module_name: &str,
+    module_path: &Path,
+    interface: &MModuleInterface,
+) -> TResult<TokenStream> {
+    let records = &interface.record_types;
+    let mut used_records = BTreeMap::new();
+    for signature in interface.function_signatures.iter() {
+        let arg_types = signature.arguments.iter().map(|argument| &argument.ty);
+        for ty in arg_types.chain(signature.outputs.iter()) {
+            collect_records(ty, records, &mut used_records)?;
+        }
+    }
+
+    let generated_records = used_records
+        .values()
+        .map(|record| generate_record(record, records))
+        .collect::<TResult<Vec<_>>>()?;
+
+    let mut signatures = interface.function_signatures.iter().collect::<Vec<_>>();
+    signatures.sort_by(|lhs, rhs| lhs.name.cmp(&rhs.name));
+    let functions = signatures
+        .into_iter()
+        .map(|signature| generate_function(signature, records))
+        .collect::<TResult<Vec<_>>>()?;
+
+    let module_path = module_path
+        .to_str()
+        .ok_or_else(|| ImportGeneratorError::InvalidUTF8Path(module_path.to_path_buf()))?;
+
+    let import = quote! {
+        // makes the crate rebuilt on changes of the imported module
+        const _: &[u8] = include_bytes!(#module_path);
+
+        #(#generated_records)*
+
+        #[marine_rs_sdk::marine]
+        #[link(wasm_import_module = #module_name)]
+        extern "C" {
+            #(#functions)*
+        }
+    };
+
+    Ok(import)
+}
+
+/// Collects records used by a type ordered by their ids, so generated code is deterministic.
+fn collect_records<'r>(
+    ty: &IType,
+    records: &'r MRecordTypes,
+    used_records: &mut BTreeMap<u64, &'r IRecordType>,
+) -> TResult<()> {
+    match ty {
+        IType::Array(ty) => collect_records(ty, records, used_records),
+        IType::Record(record_id) => {
+            let record = records
+                .get(record_id)
+                .ok_or(ImportGeneratorError::AbsentRecord(*record_id))?;
+            if used_records.insert(*record_id, record).is_some() {
+                return Ok(());
+            }
+
+            for field in record.fields.iter() {
+                collect_records(&field.ty, records, used_records)?;
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+fn generate_record(record: &IRecordType, records: &MRecordTypes) -> TResult<TokenStream> {
+    let record_ident = utils::new_ident(&record.name)?;
+    if SDK_RECORDS.contains(&record.name.as_str()) {
+        return Ok(quote! { pub use marine_rs_sdk::#record_ident; });
+    }
+
+    let fields = record
+        .fields
+        .iter()
+        .map(|field| {
+            let field_ident = utils::new_ident(&field.name)?;
+            let field_type = utils::itype_to_tokens(&field.ty, records)?;
+            let rename = if field_ident != field.name {
+                let field_name = &field.name;
+                quote! { #[marine(rename = #field_name)] }
+            } else {
+                TokenStream::new()
+            };
+
+            Ok(quote! {
+                #rename
+                pub #field_ident: #field_type
+            })
+        })
+        .collect::<TResult<Vec<_>>>()?;
+
+    let marine_attr = if record_ident != record.name {
+        let record_name = &record.name;
+        quote! { #[marine_rs_sdk::marine(name = #record_name)] }
+    } else {
+        quote! { #[marine_rs_sdk::marine] }
+    };
+
+    let generated_record = quote! {
+        #marine_attr
+        #[derive(Clone, Debug, Default)]
+        pub struct #record_ident {
+            #(#fields),*
+        }
+    };
+
+    Ok(generated_record)
+}
+
+fn generate_function(
+    signature: &MFunctionSignature,
+    records: &MRecordTypes,
+) -> TResult<TokenStream> {
+    let function_ident = utils::new_ident(&signature.name)?;
+    let link_name = if function_ident != signature.name.as_str() {
+        let function_name = signature.name.as_str();
+        quote! { #[link_name = #function_name] }
+    } else {
+        TokenStream::new()
+    };
+
+    let arguments = signature
+        .arguments
+        .iter()
+        .map(|argument| {
+            let argument_ident = utils::new_ident(&argument.name)?;
+            let argument_type = utils::itype_to_tokens(&argument.ty, records)?;
+            Ok(quote! { #argument_ident: #argument_type })
+        })
+        .collect::<TResult<Vec<_>>>()?;
+
+    let output = match signature.outputs.as_slice() {
+        [] => TokenStream::new(),
+        [output_type] => {
+            let output_type = utils::itype_to_tokens(output_type, records)?;
+            quote! { -> #output_type }
+        }
+        _ => return Err(ImportGeneratorError::ManyFnOutputsUnsupported),
+    };
+
+    let function = quote! {
+        #link_name
+        pub fn #function_ident(#(#arguments),*) #output;
+    };
+
+    Ok(function)
+}
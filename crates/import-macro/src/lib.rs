@@ -0,0 +1,77 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Defines the `marine_import!` macro that generates records and an extern block to call
+//! functions of another module, they are taken from the interface embedded into the compiled module.
+
+#![doc(html_root_url = "https://docs.rs/marine-import-macro/0.6.10")]
+#![deny(
+    dead_code,
+    nonstandard_style,
+    unused_imports,
+    unused_mut,
+    unused_variables,
+    unused_unsafe,
+    unreachable_patterns
+)]
+#![warn(rust_2018_idioms)]
+#![recursion_limit = "1024"]
+
+mod errors;
+mod import_generator;
+mod marine_import_impl;
+mod utils;
+
+use marine_import_impl::marine_import_impl;
+use proc_macro::TokenStream;
+use syn::spanned::Spanned;
+
+use std::path::PathBuf;
+
+pub(crate) type TResult<T> = std::result::Result<T, errors::ImportGeneratorError>;
+
+/// This macro generates records and an extern block to call functions of another module,
+/// they are taken from the interface embedded into the compiled module:
+///```ignore
+/// marine_import!("path/to/greeting.wasm");
+///
+/// #[marine]
+/// pub fn greet_twice(name: String) -> String {
+///     let once = greeting(name);
+///     greeting(once)
+/// }
+///```
+///
+/// A relative path is resolved from the crate root. The module is imported by its file name,
+/// another name of the module in a service could be specified explicitly:
+///```ignore
+/// marine_import!("path/to/greeting_v2.wasm", module_name = "greeting");
+///```
+#[proc_macro]
+pub fn marine_import(input: TokenStream) -> TokenStream {
+    let input: proc_macro2::TokenStream = input.into();
+    let input_span = input.span();
+    // Span::source_file is unstable, so paths are resolved from the crate root instead
+    let crate_root = std::env::var_os("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_default();
+
+    match marine_import_impl(input, crate_root) {
+        Ok(stream) => stream,
+        Err(e) => syn::Error::new(input_span, e).to_compile_error(),
+    }
+    .into()
+}
@@ -0,0 +1,79 @@
+/*
+ * Copyright 2021 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::errors::ImportGeneratorError;
+use crate::import_generator::generate_import;
+use crate::TResult;
+
+use marine_it_parser::module_raw_interface;
+use proc_macro2::TokenStream;
+use syn::parse::Parse;
+use syn::parse::ParseStream;
+
+use std::path::PathBuf;
+
+/// Arguments of the macro: `marine_import!("path/to/module.wasm", module_name = "name")`.
+struct ImportArgs {
+    module_path: syn::LitStr,
+    module_name: Option<syn::LitStr>,
+}
+
+impl Parse for ImportArgs {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let module_path = input.parse::<syn::LitStr>()?;
+        if input.is_empty() {
+            return Ok(Self {
+                module_path,
+                module_name: None,
+            });
+        }
+
+        input.parse::<syn::Token![,]>()?;
+        let name = input.parse::<syn::Ident>()?;
+        if name != "module_name" {
+            return Err(syn::Error::new(name.span(), "expected module_name"));
+        }
+        input.parse::<syn::Token![=]>()?;
+        let module_name = input.parse::<syn::LitStr>()?;
+        // allow a trailing comma
+        let _ = input.parse::<Option<syn::Token![,]>>()?;
+
+        Ok(Self {
+            module_path,
+            module_name: Some(module_name),
+        })
+    }
+}
+
+/// Generates records and an extern block to call functions of the provided module from
+/// another module. A relative path is resolved from `crate_root`.
+pub(crate) fn marine_import_impl(input: TokenStream, crate_root: PathBuf) -> TResult<TokenStream> {
+    let args = syn::parse2::<ImportArgs>(input)?;
+
+    let module_path = crate_root.join(args.module_path.value());
+    let module_name = match args.module_name {
+        Some(module_name) => module_name.value(),
+        // modules are imported by their names in a service config that are usually file names
+        None => module_path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .map(ToString::to_string)
+            .ok_or_else(|| ImportGeneratorError::InvalidUTF8Path(module_path.clone()))?,
+    };
+
+    let interface = module_raw_interface(&module_path)?;
+    generate_import(&module_name, &module_path, &interface)
+}
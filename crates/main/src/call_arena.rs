@@ -0,0 +1,235 @@
+/*
+ * Copyright 2021 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Contains a per-call bump arena that keeps buffers returned to a host alive until the start
+//! of the next export call. The arena is enabled by the `arena` feature, otherwise returned
+//! buffers are saved as objects to release and freed only when a host calls `release_objects`.
+
+#[cfg(feature = "arena")]
+use std::cell::RefCell;
+
+#[cfg(feature = "arena")]
+thread_local!(static CALL_ARENA: RefCell<CallArena> = RefCell::new(CallArena::new()));
+
+/// Keeps the given buffer alive until it is lifted by a host and returns a pointer to its data.
+/// Intended to be used by generated code for serialized records and vectors.
+pub fn retain_buffer<T: Copy + 'static>(buffer: Vec<T>) -> *const T {
+    #[cfg(feature = "arena")]
+    {
+        CALL_ARENA.with(|arena| arena.borrow_mut().alloc_copy(&buffer))
+    }
+
+    #[cfg(not(feature = "arena"))]
+    {
+        let buffer_ptr = buffer.as_ptr();
+        crate::result::add_object_to_release(Box::new(buffer));
+
+        buffer_ptr
+    }
+}
+
+/// Releases everything retained during the previous export call. Generated code calls it
+/// at the start of each export function, it does nothing if the `arena` feature is disabled.
+pub fn reset_call_arena() {
+    #[cfg(feature = "arena")]
+    {
+        crate::debug_log!(format!(
+            "sdk.reset_call_arena: {:?}\n",
+            call_arena_stats().current_call
+        ));
+
+        unsafe { crate::result::release_objects() };
+        CALL_ARENA.with(|arena| arena.borrow_mut().reset());
+    }
+}
+
+/// Counters of memory retained by the arena during one export call.
+#[cfg(feature = "arena")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CallArenaCounters {
+    /// Count of buffers copied into the arena.
+    pub buffers: usize,
+
+    /// Size of all buffers copied into the arena in bytes.
+    pub bytes: usize,
+
+    /// Count of objects saved by `add_object_to_release`.
+    pub objects: usize,
+}
+
+/// Describes memory retained by the arena.
+#[cfg(feature = "arena")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CallArenaStats {
+    /// Memory retained by the currently running or the last finished export call.
+    pub current_call: CallArenaCounters,
+
+    /// Memory retained by the export call preceding the current one.
+    pub previous_call: CallArenaCounters,
+
+    /// Overall size of memory owned by the arena in bytes.
+    pub capacity: usize,
+}
+
+/// Returns counters of memory retained by the arena.
+#[cfg(feature = "arena")]
+pub fn call_arena_stats() -> CallArenaStats {
+    CALL_ARENA.with(|arena| arena.borrow().stats())
+}
+
+#[cfg(feature = "arena")]
+pub(crate) fn count_retained_object() {
+    CALL_ARENA.with(|arena| arena.borrow_mut().current_call.objects += 1);
+}
+
+/// Minimal size of one arena chunk in words.
+#[cfg(feature = "arena")]
+const CHUNK_WORDS: usize = 512;
+
+#[cfg(feature = "arena")]
+struct CallArena {
+    // u64 is used as a chunk element to keep all allocations 8 byte aligned,
+    // chunks are never resized, so pointers to their data stay valid until reset
+    chunks: Vec<Vec<u64>>,
+    // offset in words of the first free word in the last chunk
+    offset: usize,
+    current_call: CallArenaCounters,
+    previous_call: CallArenaCounters,
+}
+
+#[cfg(feature = "arena")]
+impl CallArena {
+    fn new() -> Self {
+        Self {
+            chunks: Vec::new(),
+            offset: 0,
+            current_call: <_>::default(),
+            previous_call: <_>::default(),
+        }
+    }
+
+    fn alloc_copy<T: Copy>(&mut self, data: &[T]) -> *const T {
+        debug_assert!(std::mem::align_of::<T>() <= std::mem::align_of::<u64>());
+
+        let size = std::mem::size_of_val(data);
+        let ptr = self.alloc(size);
+        // it's safe because alloc returns a region of at least size bytes
+        // that doesn't overlap with data
+        unsafe { std::ptr::copy_nonoverlapping(data.as_ptr() as *const u8, ptr, size) };
+
+        self.current_call.buffers += 1;
+        self.current_call.bytes += size;
+
+        ptr as _
+    }
+
+    fn alloc(&mut self, size: usize) -> *mut u8 {
+        let word_size = std::mem::size_of::<u64>();
+        let words = (size + word_size - 1) / word_size;
+
+        let fits_last_chunk = match self.chunks.last() {
+            Some(chunk) => chunk.len() - self.offset >= words,
+            None => false,
+        };
+
+        if !fits_last_chunk {
+            let chunk_words = std::cmp::max(words, CHUNK_WORDS);
+            self.chunks.push(vec![0; chunk_words]);
+            self.offset = 0;
+
+            #[cfg(feature = "memory_stats")]
+            crate::memory_stats::on_arena_resized(self.capacity());
+        }
+
+        // the last chunk always exists here
+        let chunk = self.chunks.last_mut().unwrap();
+        // it's safe because offset + words doesn't exceed the chunk length
+        let ptr = unsafe { chunk.as_mut_ptr().add(self.offset) };
+        self.offset += words;
+
+        ptr as _
+    }
+
+    fn reset(&mut self) {
+        // only the largest chunk is kept to reuse its memory in the next call
+        if self.chunks.len() > 1 {
+            let chunks = std::mem::take(&mut self.chunks);
+            self.chunks
+                .extend(chunks.into_iter().max_by_key(|chunk| chunk.len()));
+        }
+
+        self.offset = 0;
+        self.previous_call = std::mem::take(&mut self.current_call);
+
+        #[cfg(feature = "memory_stats")]
+        crate::memory_stats::on_arena_resized(self.capacity());
+    }
+
+    fn stats(&self) -> CallArenaStats {
+        CallArenaStats {
+            current_call: self.current_call,
+            previous_call: self.previous_call,
+            capacity: self.capacity(),
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        self.chunks.iter().map(|chunk| chunk.len()).sum::<usize>() * std::mem::size_of::<u64>()
+    }
+}
+
+#[cfg(all(test, feature = "arena"))]
+mod tests {
+    use super::CallArena;
+    use super::CHUNK_WORDS;
+
+    #[test]
+    fn buffers_are_copied_and_aligned() {
+        let mut arena = CallArena::new();
+
+        let bytes_ptr = arena.alloc_copy(&[1u8, 2, 3]);
+        let words_ptr = arena.alloc_copy(&[4u32, 5]);
+
+        let bytes = unsafe { std::slice::from_raw_parts(bytes_ptr, 3) };
+        let words = unsafe { std::slice::from_raw_parts(words_ptr, 2) };
+
+        assert_eq!(bytes, &[1, 2, 3]);
+        assert_eq!(words, &[4, 5]);
+        assert_eq!(words_ptr as usize % std::mem::align_of::<u64>(), 0);
+
+        let stats = arena.stats();
+        assert_eq!(stats.current_call.buffers, 2);
+        assert_eq!(stats.current_call.bytes, 11);
+    }
+
+    #[test]
+    fn reset_keeps_largest_chunk() {
+        let mut arena = CallArena::new();
+
+        arena.alloc_copy(&[0u8; 16]);
+        arena.alloc_copy(&vec![0u64; 2 * CHUNK_WORDS]);
+        assert_eq!(arena.chunks.len(), 2);
+
+        arena.reset();
+
+        let stats = arena.stats();
+        assert_eq!(arena.chunks.len(), 1);
+        assert_eq!(stats.capacity, 2 * CHUNK_WORDS * 8);
+        assert_eq!(stats.current_call, <_>::default());
+        assert_eq!(stats.previous_call.buffers, 2);
+        assert_eq!(stats.previous_call.bytes, 16 + 2 * CHUNK_WORDS * 8);
+    }
+}
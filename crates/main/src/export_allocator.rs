@@ -14,16 +14,49 @@
  * limitations under the License.
  */
 
+use marine_alloc_elem_type::AllocElemType;
+
+use std::alloc::Layout;
+use std::cell::Cell;
+
+thread_local!(static ALLOCATION_ERROR: Cell<u32> = Cell::new(0));
+
+/// Describes why the last call of `allocate` or `deallocate` failed. Discriminants are error
+/// codes returned to a host by `get_allocation_error`, 0 is reserved for success.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AllocationError {
+    /// The provided type tag doesn't correspond to any known element type.
+    UnknownElemType = 1,
+
+    /// Size of the requested memory area overflows the address space.
+    CapacityOverflow = 2,
+
+    /// The allocator couldn't provide a memory area of the requested size.
+    OutOfMemory = 3,
+}
+
 /// Allocates memory area of specified size and type and returns its address.
 /// The allocated memory region is intended to be use as a Vec.
+///
+/// If the allocation failed, 0 is returned and the reason could be obtained
+/// with `get_allocation_error`.
 #[no_mangle]
 pub unsafe fn allocate(elem_count: usize, elem_ty: usize) -> usize {
+    set_allocation_error(None);
+
     if elem_count == 0 {
         // otherwise 1 would be returned due to the internals of Vec in Rust
         return 0;
     }
 
-    let allocated_mem = allocate_impl(elem_count, elem_ty);
+    let allocated_mem = match allocate_impl(elem_count, elem_ty) {
+        Ok(allocated_mem) => allocated_mem,
+        Err(error) => {
+            set_allocation_error(Some(error));
+            0
+        }
+    };
+
     crate::debug_log!(format!(
         "sdk.allocate: {} {} -> {}\n",
         elem_count, elem_ty, allocated_mem
@@ -32,29 +65,193 @@ pub unsafe fn allocate(elem_count: usize, elem_ty: usize) -> usize {
     allocated_mem
 }
 
-fn allocate_impl(elem_count: usize, elem_ty: usize) -> usize {
-    match elem_ty {
-        0 => allocate_vec::<u8>(elem_count), // for booleans
-        1 => allocate_vec::<u8>(elem_count),
-        2 => allocate_vec::<u16>(elem_count),
-        3 => allocate_vec::<u32>(elem_count),
-        4 => allocate_vec::<u64>(elem_count),
-        5 => allocate_vec::<i8>(elem_count),
-        6 => allocate_vec::<i16>(elem_count),
-        7 => allocate_vec::<i32>(elem_count),
-        8 => allocate_vec::<i64>(elem_count),
-        9 => allocate_vec::<f32>(elem_count),
-        10 => allocate_vec::<f64>(elem_count),
-        _ => 0,
+/// Deallocates memory area previously returned by `allocate` called with the same
+/// element count and type.
+#[no_mangle]
+pub unsafe fn deallocate(ptr: usize, elem_count: usize, elem_ty: usize) {
+    crate::debug_log!(format!(
+        "sdk.deallocate: {} {} {}\n",
+        ptr, elem_count, elem_ty
+    ));
+
+    set_allocation_error(None);
+
+    if ptr == 0 || elem_count == 0 {
+        // nothing was allocated for such arguments by allocate
+        return;
+    }
+
+    match elem_layout(elem_count, elem_ty) {
+        Ok(layout) => {
+            std::alloc::dealloc(ptr as _, layout);
+
+            #[cfg(feature = "memory_stats")]
+            crate::memory_stats::on_deallocate(layout.size());
+        }
+        Err(error) => set_allocation_error(Some(error)),
+    }
+}
+
+/// Takes ownership of a memory area returned by `allocate` called with the same element
+/// count and type. Used by the generated code, so the type of vector elements there can't
+/// differ from the element type the area was allocated for.
+pub unsafe fn take_allocated_vec<T>(
+    elem_ty: AllocElemType,
+    ptr: usize,
+    elem_count: usize,
+) -> Vec<T> {
+    assert_eq!(std::mem::size_of::<T>(), elem_ty.size());
+
+    if elem_count == 0 {
+        // allocate returns 0 for empty areas, that isn't a valid pointer for Vec
+        return Vec::new();
+    }
+
+    // the returned vector is freed by generated code, so the area stops being counted here
+    #[cfg(feature = "memory_stats")]
+    crate::memory_stats::on_deallocate(elem_count * elem_ty.size());
+
+    Vec::from_raw_parts(ptr as _, elem_count, elem_count)
+}
+
+/// Returns a code of the error occurred in the last call of `allocate` or `deallocate`,
+/// or 0 if the call succeeded.
+#[no_mangle]
+pub fn get_allocation_error() -> u32 {
+    ALLOCATION_ERROR.with(|error| error.get())
+}
+
+fn set_allocation_error(error: Option<AllocationError>) {
+    let error_code = error.map_or(0, |error| error as u32);
+    ALLOCATION_ERROR.with(|error| error.set(error_code));
+}
+
+fn allocate_impl(elem_count: usize, elem_ty: usize) -> Result<usize, AllocationError> {
+    let layout = elem_layout(elem_count, elem_ty)?;
+
+    // it's safe because elem_count is non-zero and all element types have non-zero size,
+    // so layout has non-zero size
+    let allocated_mem = unsafe { std::alloc::alloc(layout) };
+    if allocated_mem.is_null() {
+        return Err(AllocationError::OutOfMemory);
     }
+
+    #[cfg(feature = "memory_stats")]
+    crate::memory_stats::on_allocate(layout.size());
+
+    Ok(allocated_mem as usize)
+}
+
+/// Returns the layout Vec uses for a buffer of elem_count elements of the given type,
+/// so that the allocated memory could be then turned into a Vec.
+fn elem_layout(elem_count: usize, elem_ty: usize) -> Result<Layout, AllocationError> {
+    let elem_ty = AllocElemType::from_type_tag(elem_ty).ok_or(AllocationError::UnknownElemType)?;
+
+    let layout = match elem_ty {
+        // booleans are passed as u8
+        AllocElemType::Boolean | AllocElemType::U8 => Layout::array::<u8>(elem_count),
+        AllocElemType::U16 => Layout::array::<u16>(elem_count),
+        AllocElemType::U32 => Layout::array::<u32>(elem_count),
+        AllocElemType::U64 => Layout::array::<u64>(elem_count),
+        AllocElemType::I8 => Layout::array::<i8>(elem_count),
+        AllocElemType::I16 => Layout::array::<i16>(elem_count),
+        AllocElemType::I32 => Layout::array::<i32>(elem_count),
+        AllocElemType::I64 => Layout::array::<i64>(elem_count),
+        AllocElemType::F32 => Layout::array::<f32>(elem_count),
+        AllocElemType::F64 => Layout::array::<f64>(elem_count),
+    };
+
+    layout.map_err(|_| AllocationError::CapacityOverflow)
 }
 
-fn allocate_vec<T>(count: usize) -> usize {
-    // TODO: handle OOM
-    // This allocation scheme with vectors is needed to deal with internal Vec layout
-    let vec = Vec::<T>::with_capacity(count);
-    let offset = vec.as_ptr() as usize;
-    std::mem::forget(vec);
+#[cfg(all(test, not(feature = "debug")))]
+mod tests {
+    use super::allocate;
+    use super::deallocate;
+    use super::get_allocation_error;
+    use super::take_allocated_vec;
+    use super::AllocationError;
+    use marine_alloc_elem_type::AllocElemType;
+
+    #[test]
+    fn allocate_deallocate() {
+        let elem_ty = AllocElemType::U64.type_tag();
+        let ptr = unsafe { allocate(4, elem_ty) };
+
+        assert_ne!(ptr, 0);
+        assert_eq!(get_allocation_error(), 0);
+        assert_eq!(ptr % std::mem::align_of::<u64>(), 0);
+
+        unsafe { deallocate(ptr, 4, elem_ty) };
+        assert_eq!(get_allocation_error(), 0);
+    }
+
+    #[test]
+    fn allocated_memory_is_vec_compatible() {
+        let ptr = unsafe { allocate(3, AllocElemType::I32.type_tag()) };
+
+        let mut vec = unsafe { Vec::<i32>::from_raw_parts(ptr as _, 0, 3) };
+        vec.extend_from_slice(&[1, 2, 3]);
+
+        assert_eq!(vec, vec![1, 2, 3]);
+        assert_eq!(vec.as_ptr() as usize, ptr);
+    }
+
+    #[test]
+    fn taken_vec_is_vec_compatible() {
+        let ptr = unsafe { allocate(2, AllocElemType::U16.type_tag()) };
+        unsafe { (ptr as *mut [u16; 2]).write([1, 2]) };
 
-    offset
+        let vec = unsafe { take_allocated_vec::<u16>(AllocElemType::U16, ptr, 2) };
+
+        assert_eq!(vec, vec![1, 2]);
+    }
+
+    #[cfg(feature = "memory_stats")]
+    #[test]
+    fn taken_vec_is_not_counted_as_allocated() {
+        let ptr = unsafe { allocate(4, AllocElemType::U32.type_tag()) };
+        assert_eq!(crate::memory_stats::memory_stats().allocated_bytes, 16);
+
+        let vec = unsafe { take_allocated_vec::<u32>(AllocElemType::U32, ptr, 4) };
+        drop(vec);
+
+        assert_eq!(crate::memory_stats::memory_stats().allocated_bytes, 0);
+    }
+
+    #[test]
+    fn empty_allocation() {
+        let ptr = unsafe { allocate(0, AllocElemType::U8.type_tag()) };
+
+        assert_eq!(ptr, 0);
+        assert_eq!(get_allocation_error(), 0);
+    }
+
+    #[test]
+    fn unknown_elem_type() {
+        let ptr = unsafe { allocate(1, 11) };
+
+        assert_eq!(ptr, 0);
+        assert_eq!(
+            get_allocation_error(),
+            AllocationError::UnknownElemType as u32
+        );
+
+        unsafe { deallocate(42, 1, 11) };
+        assert_eq!(
+            get_allocation_error(),
+            AllocationError::UnknownElemType as u32
+        );
+    }
+
+    #[test]
+    fn capacity_overflow() {
+        let ptr = unsafe { allocate(usize::MAX, AllocElemType::F64.type_tag()) };
+
+        assert_eq!(ptr, 0);
+        assert_eq!(
+            get_allocation_error(),
+            AllocationError::CapacityOverflow as u32
+        );
+    }
 }
@@ -14,8 +14,8 @@
  * limitations under the License.
  */
 
-//! The main part of Fluence backend SDK. Contains `export_allocator`, `logger` and `result`
-//! modules.
+//! The main part of Fluence backend SDK. Contains `export_allocator`, `call_arena`, `logger`,
+//! `panic_hook` and `result` modules.
 
 #![allow(clippy::missing_safety_doc)]
 #![allow(clippy::needless_doctest_main)]
@@ -31,14 +31,33 @@
 )]
 #![warn(rust_2018_idioms)]
 
+mod call_arena;
 mod export_allocator;
 #[cfg(any(feature = "debug", feature = "logger"))]
 mod logger;
+#[cfg(feature = "memory_stats")]
+mod memory_stats;
 mod module_manifest;
+#[cfg(feature = "panic_hook")]
+mod panic_hook;
 mod result;
 mod sdk_version_embedder;
 
+pub use call_arena::retain_buffer;
+pub use call_arena::reset_call_arena;
+#[cfg(feature = "arena")]
+pub use call_arena::call_arena_stats;
+#[cfg(feature = "arena")]
+pub use call_arena::CallArenaCounters;
+#[cfg(feature = "arena")]
+pub use call_arena::CallArenaStats;
+
 pub use export_allocator::allocate;
+pub use export_allocator::deallocate;
+pub use export_allocator::get_allocation_error;
+pub use export_allocator::take_allocated_vec;
+pub use export_allocator::AllocationError;
+pub use marine_alloc_elem_type::AllocElemType;
 
 #[cfg(feature = "logger")]
 pub use logger::WasmLoggerBuilder;
@@ -54,6 +73,18 @@ pub use result::set_result_size;
 pub use result::release_objects;
 pub use result::add_object_to_release;
 
+#[cfg(feature = "memory_stats")]
+pub use memory_stats::memory_stats;
+#[cfg(feature = "memory_stats")]
+pub use memory_stats::MemoryStats;
+
+#[cfg(feature = "panic_hook")]
+pub use panic_hook::install_panic_hook;
+#[cfg(feature = "panic_hook")]
+pub use panic_hook::take_panic_report;
+#[cfg(feature = "panic_hook")]
+pub use panic_hook::PanicReport;
+
 pub use module_manifest::MANIFEST_SECTION_NAME;
 pub use sdk_version_embedder::VERSION_SECTION_NAME;
 
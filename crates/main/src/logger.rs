@@ -91,6 +91,8 @@ struct WasmLogger {
 /// Build logger for the Fluence network, allows specifying target map and log level while building.
 pub struct WasmLoggerBuilder {
     wasm_logger: WasmLogger,
+    #[cfg(feature = "panic_hook")]
+    panic_hook: bool,
 }
 
 impl WasmLoggerBuilder {
@@ -111,7 +113,11 @@ impl WasmLoggerBuilder {
             default_log_level,
         };
 
-        Self { wasm_logger }
+        Self {
+            wasm_logger,
+            #[cfg(feature = "panic_hook")]
+            panic_hook: true,
+        }
     }
 
     /// Set the log level.
@@ -127,6 +133,14 @@ impl WasmLoggerBuilder {
         self
     }
 
+    /// Enable or disable the panic hook that logs panics and saves them to be fetched
+    /// by a host. It's enabled by default.
+    #[cfg(feature = "panic_hook")]
+    pub fn with_panic_hook(mut self, enabled: bool) -> Self {
+        self.panic_hook = enabled;
+        self
+    }
+
     pub fn filter(mut self, module_name: impl Into<String>, level: LevelFilter) -> Self {
         let module_name = module_name.into();
         let log_directive = LogDirective::new(module_name, level);
@@ -156,10 +170,14 @@ impl WasmLoggerBuilder {
         let max_level = self.max_log_level();
         self.sort_directives();
 
-        let Self { wasm_logger } = self;
-
-        log::set_boxed_logger(Box::new(wasm_logger))?;
+        log::set_boxed_logger(Box::new(self.wasm_logger))?;
         log::set_max_level(max_level);
+
+        #[cfg(feature = "panic_hook")]
+        if self.panic_hook {
+            crate::panic_hook::install_panic_hook();
+        }
+
         Ok(())
     }
 
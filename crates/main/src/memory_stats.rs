@@ -0,0 +1,164 @@
+/*
+ * Copyright 2021 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Tracks memory a module retains between calls: memory allocated by a host through `allocate`,
+//! objects kept alive until a host calls `release_objects` and memory owned by the call arena.
+//! Enabled by the `memory_stats` feature.
+
+use std::any::Any;
+use std::cell::Cell;
+
+thread_local!(static MEMORY_STATS: Cell<MemoryStats> = Cell::new(MemoryStats::default()));
+
+/// Describes memory retained by a module.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Bytes allocated by `allocate` and neither freed by `deallocate` nor taken by generated code.
+    pub allocated_bytes: usize,
+
+    /// Count of objects kept alive until a host calls `release_objects`.
+    pub objects_to_release: usize,
+
+    /// Approximate size in bytes of objects kept alive until a host calls `release_objects`.
+    /// Heap buffers are counted only for `String` and vectors of scalar types, that generated
+    /// code usually saves, other objects are counted only by their own size without memory
+    /// they point to.
+    pub objects_to_release_bytes: usize,
+
+    /// Bytes owned by the call arena enabled by the `arena` feature.
+    pub arena_bytes: usize,
+
+    /// The maximum observed value of `allocated_bytes + objects_to_release_bytes + arena_bytes`.
+    pub peak_bytes: usize,
+}
+
+/// Returns memory usage counters of this module.
+pub fn memory_stats() -> MemoryStats {
+    MEMORY_STATS.with(|stats| stats.get())
+}
+
+pub(crate) fn on_allocate(bytes: usize) {
+    update_stats(|stats| stats.allocated_bytes += bytes);
+}
+
+pub(crate) fn on_deallocate(bytes: usize) {
+    update_stats(|stats| stats.allocated_bytes = stats.allocated_bytes.saturating_sub(bytes));
+}
+
+pub(crate) fn on_object_retained(object: &dyn Any) {
+    let object_size = object_size(object);
+
+    update_stats(|stats| {
+        stats.objects_to_release += 1;
+        stats.objects_to_release_bytes += object_size;
+    });
+}
+
+pub(crate) fn on_objects_released() {
+    update_stats(|stats| {
+        stats.objects_to_release = 0;
+        stats.objects_to_release_bytes = 0;
+    });
+}
+
+#[cfg(feature = "arena")]
+pub(crate) fn on_arena_resized(arena_bytes: usize) {
+    update_stats(|stats| stats.arena_bytes = arena_bytes);
+}
+
+fn update_stats(updater: impl FnOnce(&mut MemoryStats)) {
+    MEMORY_STATS.with(|stats| {
+        let mut new_stats = stats.get();
+        updater(&mut new_stats);
+
+        let current_bytes =
+            new_stats.allocated_bytes + new_stats.objects_to_release_bytes + new_stats.arena_bytes;
+        new_stats.peak_bytes = std::cmp::max(new_stats.peak_bytes, current_bytes);
+
+        stats.set(new_stats);
+    });
+}
+
+/// Returns size of the given object including its heap buffer for types generated code
+/// usually saves, and only size of the object itself for other types.
+fn object_size(object: &dyn Any) -> usize {
+    fn vec_size<T: 'static>(object: &dyn Any) -> Option<usize> {
+        object
+            .downcast_ref::<Vec<T>>()
+            .map(|vec| std::mem::size_of::<Vec<T>>() + vec.capacity() * std::mem::size_of::<T>())
+    }
+
+    if let Some(string) = object.downcast_ref::<String>() {
+        return std::mem::size_of::<String>() + string.capacity();
+    }
+
+    vec_size::<u8>(object)
+        .or_else(|| vec_size::<u16>(object))
+        .or_else(|| vec_size::<u32>(object))
+        .or_else(|| vec_size::<u64>(object))
+        .or_else(|| vec_size::<i8>(object))
+        .or_else(|| vec_size::<i16>(object))
+        .or_else(|| vec_size::<i32>(object))
+        .or_else(|| vec_size::<i64>(object))
+        .or_else(|| vec_size::<f32>(object))
+        .or_else(|| vec_size::<f64>(object))
+        .or_else(|| vec_size::<bool>(object))
+        .unwrap_or_else(|| std::mem::size_of_val(object))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn peak_is_kept_after_release() {
+        on_allocate(100);
+        on_object_retained(&vec![0u32; 10]);
+
+        let stats = memory_stats();
+        let object_size = std::mem::size_of::<Vec<u32>>() + 40;
+        assert_eq!(stats.allocated_bytes, 100);
+        assert_eq!(stats.objects_to_release, 1);
+        assert_eq!(stats.objects_to_release_bytes, object_size);
+        assert_eq!(stats.peak_bytes, 100 + object_size);
+
+        on_objects_released();
+        on_deallocate(100);
+
+        let stats = memory_stats();
+        assert_eq!(stats.allocated_bytes, 0);
+        assert_eq!(stats.objects_to_release, 0);
+        assert_eq!(stats.objects_to_release_bytes, 0);
+        assert_eq!(stats.peak_bytes, 100 + object_size);
+    }
+
+    #[cfg(feature = "arena")]
+    #[test]
+    fn arena_bytes_are_counted_in_peak() {
+        on_arena_resized(64);
+        on_arena_resized(16);
+
+        let stats = memory_stats();
+        assert_eq!(stats.arena_bytes, 16);
+        assert!(stats.peak_bytes >= 64);
+    }
+
+    #[test]
+    fn string_size_includes_buffer() {
+        let string = String::with_capacity(42);
+        assert_eq!(object_size(&string), std::mem::size_of::<String>() + 42);
+    }
+}
@@ -0,0 +1,149 @@
+/*
+ * Copyright 2021 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! This module contains a panic hook that saves a message and location of a panic, so that
+//! a host could fetch it after a Wasm trap caused by the panic. If the `logger` feature is
+//! enabled, the panic is also logged by `log_utf8_string`.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::fmt;
+use std::panic::Location;
+
+thread_local!(static PANIC_REPORT: RefCell<Option<PanicReport>> = RefCell::new(None));
+
+/// Describes a panic occurred in a module.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PanicReport {
+    /// Panic message or an empty string if a panic payload isn't a string.
+    pub message: String,
+
+    /// Source file where the panic occurred.
+    pub file: String,
+
+    pub line: u32,
+
+    pub column: u32,
+}
+
+impl PanicReport {
+    pub(crate) fn new(payload: &dyn Any, location: Option<&Location<'_>>) -> Self {
+        let message = match payload.downcast_ref::<&str>() {
+            Some(message) => message.to_string(),
+            None => payload
+                .downcast_ref::<String>()
+                .cloned()
+                .unwrap_or_default(),
+        };
+
+        let (file, line, column) = match location {
+            Some(location) => (
+                location.file().to_string(),
+                location.line(),
+                location.column(),
+            ),
+            None => (String::new(), 0, 0),
+        };
+
+        Self {
+            message,
+            file,
+            line,
+            column,
+        }
+    }
+}
+
+impl fmt::Display for PanicReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "panicked at '{}', {}:{}:{}",
+            self.message, self.file, self.line, self.column
+        )
+    }
+}
+
+/// Sets the panic hook that saves the last panic to be fetched by a host and then calls
+/// the previously set hook. It's called by `WasmLoggerBuilder::build` unless disabled
+/// by `with_panic_hook(false)`.
+pub fn install_panic_hook() {
+    let previous_hook = std::panic::take_hook();
+
+    std::panic::set_hook(Box::new(move |panic_info| {
+        let report = PanicReport::new(panic_info.payload(), panic_info.location());
+
+        #[cfg(feature = "logger")]
+        {
+            let level = log::Level::Error as i32;
+            let target = 0i32;
+            let msg = report.to_string();
+            crate::logger::log_utf8_string(level, target, msg.as_ptr() as _, msg.len() as _);
+        }
+
+        save_panic_report(report);
+        previous_hook(panic_info);
+    }));
+}
+
+/// Returns the last panic report and clears it, so the next call returns None until
+/// a new panic occurs.
+pub fn take_panic_report() -> Option<PanicReport> {
+    PANIC_REPORT.with(|report| report.try_borrow_mut().ok()?.take())
+}
+
+fn save_panic_report(new_report: PanicReport) {
+    PANIC_REPORT.with(|report| {
+        // the report could be borrowed if the panic occurred inside take_panic_report
+        if let Ok(mut report) = report.try_borrow_mut() {
+            *report = Some(new_report);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_from_str_payload() {
+        let location = Location::caller();
+        let report = PanicReport::new(&"something went wrong", Some(location));
+
+        assert_eq!(report.message, "something went wrong");
+        assert_eq!(report.file, location.file());
+        assert_eq!(report.line, location.line());
+        assert_eq!(report.column, location.column());
+    }
+
+    #[test]
+    fn report_from_string_payload() {
+        let payload = format!("value is {}", 42);
+        let report = PanicReport::new(&payload, None);
+
+        assert_eq!(report.message, "value is 42");
+        assert_eq!(report.to_string(), "panicked at 'value is 42', :0:0");
+    }
+
+    #[test]
+    fn report_is_taken_once() {
+        let report = PanicReport::new(&"panic", None);
+        save_panic_report(report.clone());
+
+        assert_eq!(take_panic_report(), Some(report));
+        assert_eq!(take_panic_report(), None);
+    }
+}
@@ -15,50 +15,49 @@
  */
 
 //! Contains ad-hoc implementations of returning complex data types from function calls
-//! by two global variables that contain pointer and size. Will be refactored after multi-value
+//! by two registers that contain pointer and size. Will be refactored after multi-value
 //! support in Wasmer.
+//!
+//! Registers are thread local, so calls executed by a host on different threads
+//! don't interfere with each other.
 
-use std::sync::atomic::AtomicUsize;
+use std::cell::Cell;
 use std::cell::RefCell;
 use std::any::Any;
 
-static mut RESULT_PTR: AtomicUsize = AtomicUsize::new(0);
-static mut RESULT_SIZE: AtomicUsize = AtomicUsize::new(0);
+thread_local!(static RESULT_PTR: Cell<usize> = Cell::new(0));
+thread_local!(static RESULT_SIZE: Cell<usize> = Cell::new(0));
 
 thread_local!(static OBJECTS_TO_RELEASE: RefCell<Vec<Box<dyn Any>>> = RefCell::new(Vec::new()));
 
 #[no_mangle]
 pub unsafe fn get_result_ptr() -> usize {
-    crate::debug_log!(format!(
-        "sdk.get_result_ptr, returns {}\n",
-        *RESULT_PTR.get_mut()
-    ));
+    let result_ptr = RESULT_PTR.with(Cell::get);
+    crate::debug_log!(format!("sdk.get_result_ptr, returns {}\n", result_ptr));
 
-    *RESULT_PTR.get_mut()
+    result_ptr
 }
 
 #[no_mangle]
 pub unsafe fn get_result_size() -> usize {
-    crate::debug_log!(format!(
-        "sdk.get_result_size, returns {}\n",
-        *RESULT_SIZE.get_mut()
-    ));
+    let result_size = RESULT_SIZE.with(Cell::get);
+    crate::debug_log!(format!("sdk.get_result_size, returns {}\n", result_size));
 
-    *RESULT_SIZE.get_mut()
+    result_size
 }
 
 #[no_mangle]
 pub unsafe fn set_result_ptr(ptr: usize) {
     crate::debug_log!(format!("sdk.set_result_ptr: {}\n", ptr));
 
-    *RESULT_PTR.get_mut() = ptr;
+    RESULT_PTR.with(|result_ptr| result_ptr.set(ptr));
 }
 
 #[no_mangle]
 pub unsafe fn set_result_size(size: usize) {
     crate::debug_log!(format!("sdk.set_result_size: {}\n", size));
 
-    *RESULT_SIZE.get_mut() = size;
+    RESULT_SIZE.with(|result_size| result_size.set(size));
 }
 
 #[no_mangle]
@@ -68,12 +67,74 @@ pub unsafe fn release_objects() {
         while let Some(object) = objects.pop() {
             drop(object);
         }
-    })
+    });
+
+    #[cfg(feature = "memory_stats")]
+    crate::memory_stats::on_objects_released();
 }
 
 pub fn add_object_to_release(object: Box<dyn Any>) {
+    #[cfg(feature = "arena")]
+    crate::call_arena::count_retained_object();
+
+    #[cfg(feature = "memory_stats")]
+    crate::memory_stats::on_object_retained(&*object);
+
     OBJECTS_TO_RELEASE.with(|objects| {
         let mut objects = objects.borrow_mut();
         objects.push(object);
     });
 }
+
+// debug_log passes message pointers as i32, so it works only on wasm32
+#[cfg(all(test, not(feature = "debug")))]
+mod tests {
+    use super::get_result_ptr;
+    use super::get_result_size;
+    use super::set_result_ptr;
+    use super::set_result_size;
+
+    use std::sync::Arc;
+    use std::sync::Barrier;
+
+    #[test]
+    fn registers_are_thread_local() {
+        const THREADS_COUNT: usize = 8;
+
+        let barrier = Arc::new(Barrier::new(THREADS_COUNT));
+        let threads = (1..=THREADS_COUNT)
+            .map(|thread_id| {
+                let barrier = barrier.clone();
+                std::thread::spawn(move || unsafe {
+                    assert_eq!(get_result_ptr(), 0);
+                    assert_eq!(get_result_size(), 0);
+
+                    set_result_ptr(thread_id);
+                    set_result_size(2 * thread_id);
+
+                    // let all threads set their values before checking them
+                    barrier.wait();
+
+                    assert_eq!(get_result_ptr(), thread_id);
+                    assert_eq!(get_result_size(), 2 * thread_id);
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for thread in threads {
+            thread.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn registers_are_overwritten() {
+        unsafe {
+            set_result_ptr(1);
+            set_result_size(2);
+            set_result_ptr(3);
+
+            assert_eq!(get_result_ptr(), 3);
+            assert_eq!(get_result_size(), 2);
+        }
+    }
+}
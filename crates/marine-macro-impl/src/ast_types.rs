@@ -20,6 +20,8 @@ use crate::parsed_type::ParsedType;
 pub(crate) struct AstFnArgument {
     pub name: String,
     pub ty: ParsedType,
+    /// Description of the argument from the `# Arguments` section of function docs.
+    pub docs: Option<String>,
 }
 
 #[derive(Clone)]
@@ -30,12 +32,18 @@ pub(crate) struct AstFnSignature {
     // only one or zero return values are supported now,
     // waiting for adding multi-value support in Wasmer
     pub output_type: Option<ParsedType>,
+    pub docs: Option<String>,
 }
 
 #[derive(Clone)]
 pub(crate) struct AstRecord {
     pub name: String,
+    /// Name of the record in the module interface specified by `#[marine(name = "...")]`.
+    pub export_name: Option<String>,
     pub fields: AstRecordFields,
+    /// Fields excluded from the interface, they are set on lifting of a record.
+    pub skipped_fields: Vec<AstSkippedField>,
+    pub docs: Option<String>,
     pub original: syn::ItemStruct,
 }
 
@@ -56,7 +64,18 @@ pub(crate) enum AstRecordFields {
 pub(crate) struct AstRecordField {
     /// Name of the field. Can be `None` for tuples.
     pub name: Option<String>,
+    /// Name of the field in the module interface specified by `#[marine(rename = "...")]`.
+    pub export_name: Option<String>,
     pub ty: ParsedType,
+    pub docs: Option<String>,
+}
+
+/// A record field excluded from the interface by `#[marine(skip)]` or `#[marine(default = "...")]`.
+#[derive(Clone)]
+pub(crate) struct AstSkippedField {
+    pub name: String,
+    /// Path to a function returning a field value, `Default::default` is used if it's None.
+    pub default: Option<syn::ExprPath>,
 }
 
 #[derive(Clone)]
@@ -77,6 +96,8 @@ pub(crate) struct AstExternMod {
 #[derive(Clone)]
 pub(crate) struct AstFn {
     pub signature: AstFnSignature,
+    /// Name of the exported function specified by `#[marine(name = "...")]`.
+    pub export_name: Option<String>,
     pub original: syn::ItemFn,
 }
 
@@ -86,3 +107,10 @@ pub(crate) enum MarineAst {
     ExternMod(AstExternMod),
     Record(AstRecord),
 }
+
+impl AstFn {
+    /// Returns a name the function is exported with.
+    pub fn export_name(&self) -> &str {
+        self.export_name.as_ref().unwrap_or(&self.signature.name)
+    }
+}
@@ -23,6 +23,9 @@ use serde::Deserialize;
 pub struct FnArgument {
     pub name: String,
     pub ty: ParsedType,
+    /// Description of the argument taken from the `# Arguments` section of function docs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docs: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -30,15 +33,25 @@ pub struct FnSignature {
     pub name: String,
     pub arguments: Vec<FnArgument>,
     pub output_types: Vec<ParsedType>,
+    /// Doc comments of the function, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docs: Option<String>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct RecordType {
+    /// Name of the Rust type, it's used to refer to the record from other types.
     pub name: String,
+    /// Name of the record in the module interface if it differs from the Rust name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub export_name: Option<String>,
     pub fields: RecordFields,
+    /// Doc comments of the record, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docs: Option<String>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum RecordFields {
     Named(Vec<RecordField>),
     // named and unnamed variants have the same inner field types because of it's easy to handle it,
@@ -52,6 +65,9 @@ pub struct RecordField {
     // fields of tuple structs haven't got name
     pub name: Option<String>,
     pub ty: ParsedType,
+    /// Doc comments of the field, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docs: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -109,7 +125,11 @@ impl From<AstRecord> for SDKAst {
 
 impl From<AstFn> for FnType {
     fn from(ast_fn_item: AstFn) -> Self {
-        let signature = ast_fn_item.signature.into();
+        let mut signature: FnSignature = ast_fn_item.signature.into();
+        // functions are called by their export names, so the Rust name isn't needed here
+        if let Some(export_name) = ast_fn_item.export_name {
+            signature.name = export_name;
+        }
 
         Self { signature }
     }
@@ -130,7 +150,9 @@ impl From<AstRecord> for RecordType {
     fn from(ast_record_item: AstRecord) -> Self {
         Self {
             name: ast_record_item.name,
+            export_name: ast_record_item.export_name,
             fields: ast_record_item.fields.into(),
+            docs: ast_record_item.docs,
         }
     }
 }
@@ -164,6 +186,7 @@ impl From<AstFnSignature> for FnSignature {
             name: ast_fn_sig.name,
             arguments,
             output_types: output_type,
+            docs: ast_fn_sig.docs,
         }
     }
 }
@@ -173,6 +196,7 @@ impl From<AstFnArgument> for FnArgument {
         Self {
             name: ast_fn_arg.name,
             ty: ast_fn_arg.ty,
+            docs: ast_fn_arg.docs,
         }
     }
 }
@@ -189,8 +213,9 @@ impl From<AstExternFn> for ExternFnType {
 impl From<AstRecordField> for RecordField {
     fn from(ast_record_field: AstRecordField) -> Self {
         Self {
-            name: ast_record_field.name,
+            name: ast_record_field.export_name.or(ast_record_field.name),
             ty: ast_record_field.ty,
+            docs: ast_record_field.docs,
         }
     }
 }
@@ -35,14 +35,23 @@ mod export_ast_types;
 mod marine_macro_impl;
 mod parsed_type;
 mod parse_macro_input;
+mod section_encoding;
 mod token_stream_generator;
 mod utils;
 mod wasm_type;
 
+pub use marine_alloc_elem_type::AllocElemType;
 pub use export_ast_types::*;
 pub use crate::marine_macro_impl::marine;
 pub use parsed_type::ParsedType;
 pub use parsed_type::PassingStyle;
+pub use section_encoding::decode_section;
+pub use section_encoding::encode_sdk_ast;
+pub use section_encoding::merge_sections;
+pub use section_encoding::SectionDecodeError;
+pub use section_encoding::SectionEncoding;
+pub use section_encoding::SECTION_ENTRY_MAGIC;
+pub use section_encoding::SECTION_FORMAT_VERSION;
 pub use token_stream_generator::GENERATED_WRAPPER_FUNC_PREFIX;
 pub use token_stream_generator::GENERATED_SECTION_PREFIX;
 pub use token_stream_generator::GENERATED_GLOBAL_PREFIX;
@@ -14,16 +14,18 @@
  * limitations under the License.
  */
 
+use crate::parse_macro_input::MacroAttrs;
 use crate::parse_macro_input::ParseMacroInput;
 
 use proc_macro2::TokenStream;
 use quote::ToTokens;
 use syn::Result;
 
-pub fn marine(tokens: TokenStream) -> Result<TokenStream> {
+pub fn marine(attrs: TokenStream, tokens: TokenStream) -> Result<TokenStream> {
+    let attrs = syn::parse2::<MacroAttrs>(attrs)?;
     let item = syn::parse2::<syn::Item>(tokens)?;
     // convert proc_macro2 token to internal AST type
-    let marine_ast_item = item.parse_macro_input()?;
+    let marine_ast_item = item.parse_macro_input(attrs)?;
 
     // convert internal AST type to sequence of tokens
     let mut tokens = TokenStream::new();
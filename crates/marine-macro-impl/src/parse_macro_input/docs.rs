@@ -0,0 +1,111 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+const DOC_ATTRIBUTE_NAME: &str = "doc";
+const ARGUMENTS_SECTION_NAME: &str = "arguments";
+
+/// Collects doc comments (`///`, `/** */` and `#[doc = "..."]`) of an item into one string,
+/// a leading space left by `///` is removed from each line.
+pub(super) fn collect_docs(attrs: &[syn::Attribute]) -> Option<String> {
+    let lines = attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident(DOC_ATTRIBUTE_NAME))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(syn::Meta::NameValue(syn::MetaNameValue {
+                lit: syn::Lit::Str(doc),
+                ..
+            })) => Some(doc.value()),
+            _ => None,
+        })
+        .flat_map(|doc| {
+            // split is used instead of lines to keep empty lines of docs
+            doc.split('\n')
+                .map(|line| {
+                    line.strip_prefix(' ')
+                        .unwrap_or(line)
+                        .trim_end()
+                        .to_string()
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let first_line = lines.iter().position(|line| !line.is_empty())?;
+    let last_line = lines.iter().rposition(|line| !line.is_empty())?;
+
+    Some(lines[first_line..=last_line].join("\n"))
+}
+
+/// Extracts a description of an argument from the `# Arguments` section of function docs
+/// written in the rustdoc style:
+///```ignore
+/// /// # Arguments
+/// ///
+/// /// * `name` - name of the user,
+/// ///   could span several lines
+/// ```
+pub(super) fn argument_docs(fn_docs: &str, argument_name: &str) -> Option<String> {
+    let mut lines = fn_docs
+        .lines()
+        .skip_while(|line| !is_arguments_header(line))
+        .skip(1)
+        .take_while(|line| !line.trim_start().starts_with('#'));
+
+    let mut description = lines.find_map(|line| argument_description(line, argument_name))?;
+    // continuation lines of a list item are indented and don't start a new item
+    for line in lines {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || !line.starts_with(' ') || is_list_item(trimmed) {
+            break;
+        }
+
+        description.push(' ');
+        description.push_str(trimmed);
+    }
+
+    Some(description)
+}
+
+fn is_arguments_header(line: &str) -> bool {
+    let line = line.trim();
+    line.starts_with('#')
+        && line
+            .trim_start_matches('#')
+            .trim()
+            .eq_ignore_ascii_case(ARGUMENTS_SECTION_NAME)
+}
+
+fn is_list_item(line: &str) -> bool {
+    line.starts_with("* ") || line.starts_with("- ")
+}
+
+/// Returns a description from a list item like "* `name` - description".
+fn argument_description(line: &str, argument_name: &str) -> Option<String> {
+    let line = line.trim();
+    if !is_list_item(line) {
+        return None;
+    }
+
+    let item = line[2..].trim_start();
+    let quoted_name = format!("`{}`", argument_name);
+    let description = item.strip_prefix(&quoted_name)?.trim_start();
+    let description = description
+        .strip_prefix('-')
+        .or_else(|| description.strip_prefix(':'))
+        .unwrap_or(description);
+
+    Some(description.trim().to_string())
+}
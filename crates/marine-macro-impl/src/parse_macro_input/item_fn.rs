@@ -14,6 +14,8 @@
  * limitations under the License.
  */
 
+use super::docs;
+use super::MacroAttrs;
 use super::ParseMacroInput;
 use crate::ast_types;
 use crate::ParsedType;
@@ -26,8 +28,8 @@ use syn::Result;
 use syn::spanned::Spanned;
 
 impl ParseMacroInput for syn::ItemFn {
-    fn parse_macro_input(self) -> Result<MarineAst> {
-        let signature = try_to_ast_signature(self.sig.clone(), self.vis.clone())?;
+    fn parse_macro_input(self, attrs: MacroAttrs) -> Result<MarineAst> {
+        let signature = try_to_ast_signature(self.sig.clone(), self.vis.clone(), &self.attrs)?;
 
         // this check specific only for export functions
         let parsed_args = signature
@@ -40,6 +42,7 @@ impl ParseMacroInput for syn::ItemFn {
 
         let ast_fn = MarineAst::Function(AstFn {
             signature,
+            export_name: attrs.export_name.map(|name| name.value()),
             original: self,
         });
         Ok(ast_fn)
@@ -49,11 +52,14 @@ impl ParseMacroInput for syn::ItemFn {
 pub(super) fn try_to_ast_signature(
     signature: syn::Signature,
     visibility: syn::Visibility,
+    attrs: &[syn::Attribute],
 ) -> Result<ast_types::AstFnSignature> {
     use quote::ToTokens;
 
     check_function(&signature)?;
 
+    let docs = docs::collect_docs(attrs);
+
     let syn::Signature { inputs, output, .. } = signature;
 
     let arguments = inputs
@@ -78,7 +84,10 @@ pub(super) fn try_to_ast_signature(
                 .unwrap_or_default()
                 .to_string();
             let ty = ParsedType::from_type(pat.ty.as_ref())?;
-            let ast_arg = AstFnArgument { name, ty };
+            let docs = docs
+                .as_ref()
+                .and_then(|docs| docs::argument_docs(docs, &name));
+            let ast_arg = AstFnArgument { name, ty, docs };
 
             Ok(ast_arg)
         })
@@ -91,6 +100,7 @@ pub(super) fn try_to_ast_signature(
         name: signature.ident.to_string(),
         arguments,
         output_type,
+        docs,
     };
 
     Ok(ast_function_item)
@@ -14,6 +14,7 @@
  * limitations under the License.
  */
 
+use super::MacroAttrs;
 use super::ParseMacroInput;
 use crate::ast_types;
 use crate::ast_types::MarineAst;
@@ -27,7 +28,14 @@ const LINK_NAME_DIRECTIVE_NAME: &str = "link_name";
 const WASM_IMPORT_MODULE_DIRECTIVE_NAME: &str = "wasm_import_module";
 
 impl ParseMacroInput for syn::ItemForeignMod {
-    fn parse_macro_input(self) -> Result<MarineAst> {
+    fn parse_macro_input(self, attrs: MacroAttrs) -> Result<MarineAst> {
+        if let Some(export_name) = attrs.export_name {
+            return syn_error!(
+                export_name.span(),
+                "name couldn't be applied to an extern block, use #[link_name] for imports"
+            );
+        }
+
         check_foreign_section(&self)?;
 
         let wasm_import_module: Option<String> = parse_wasm_import_module(&self);
@@ -157,7 +165,11 @@ fn parse_raw_foreign_item(raw_item: syn::ForeignItem) -> Result<ast_types::AstEx
         None => None,
     };
 
-    let signature = super::item_fn::try_to_ast_signature(function_item.sig, function_item.vis)?;
+    let signature = super::item_fn::try_to_ast_signature(
+        function_item.sig,
+        function_item.vis,
+        &function_item.attrs,
+    )?;
     let ast_extern_fn_item = ast_types::AstExternFn {
         link_name,
         signature,
@@ -14,10 +14,14 @@
  * limitations under the License.
  */
 
+use super::docs;
+use super::take_field_attrs;
+use super::MacroAttrs;
 use super::ParseMacroInput;
 use crate::ast_types;
 use crate::ast_types::AstRecordField;
 use crate::ast_types::AstRecordFields;
+use crate::ast_types::AstSkippedField;
 use crate::ast_types::MarineAst;
 use crate::syn_error;
 use crate::parsed_type::ParsedType;
@@ -26,21 +30,26 @@ use syn::Result;
 use syn::spanned::Spanned;
 
 impl ParseMacroInput for syn::ItemStruct {
-    fn parse_macro_input(self) -> Result<MarineAst> {
+    fn parse_macro_input(mut self, attrs: MacroAttrs) -> Result<MarineAst> {
         check_record(&self)?;
 
-        let fields = match &self.fields {
-            syn::Fields::Named(named_fields) => &named_fields.named,
-            _ => return syn_error!(self.span(), "only named fields are allowed in structs"),
+        let span = self.span();
+        let fields = match &mut self.fields {
+            syn::Fields::Named(named_fields) => &mut named_fields.named,
+            _ => return syn_error!(span, "only named fields are allowed in structs"),
         };
 
-        let fields = fields_into_ast(fields)?;
+        let (fields, skipped_fields) = fields_into_ast(fields)?;
         let fields = AstRecordFields::Named(fields);
 
         let name = self.ident.to_string();
+        let docs = docs::collect_docs(&self.attrs);
         let ast_record_item = ast_types::AstRecord {
             name,
+            export_name: attrs.export_name.map(|name| name.value()),
             fields,
+            skipped_fields,
+            docs,
             original: self,
         };
 
@@ -62,32 +71,53 @@ fn check_record(record: &syn::ItemStruct) -> Result<()> {
     Ok(())
 }
 
+/// Splits fields into ones exposed in the interface and skipped ones,
+/// #[marine] attributes are removed from fields.
 fn fields_into_ast(
-    fields: &syn::punctuated::Punctuated<syn::Field, syn::Token![,]>,
-) -> Result<Vec<AstRecordField>> {
-    fields
-        .iter()
-        .map(|field| {
-            check_field(field)?;
-            let name = field.ident.as_ref().map(|ident| {
-                ident
-                    .to_string()
-                    .split(' ')
-                    .last()
-                    .unwrap_or_default()
-                    .to_string()
-            });
-            let ty = ParsedType::from_type(&field.ty)?;
-
-            let record_field = AstRecordField { name, ty };
-            Ok(record_field)
-        })
-        .collect::<Result<Vec<_>>>()
+    fields: &mut syn::punctuated::Punctuated<syn::Field, syn::Token![,]>,
+) -> Result<(Vec<AstRecordField>, Vec<AstSkippedField>)> {
+    let mut record_fields = Vec::with_capacity(fields.len());
+    let mut skipped_fields = Vec::new();
+
+    for field in fields.iter_mut() {
+        let field_attrs = take_field_attrs(field)?;
+        let name = field.ident.as_ref().map(|ident| {
+            ident
+                .to_string()
+                .split(' ')
+                .last()
+                .unwrap_or_default()
+                .to_string()
+        });
+
+        if field_attrs.is_skipped() {
+            // only named fields are allowed, so name is always present here
+            let skipped_field = AstSkippedField {
+                name: name.unwrap_or_default(),
+                default: field_attrs.default,
+            };
+            skipped_fields.push(skipped_field);
+            continue;
+        }
+
+        check_field(field)?;
+        let ty = ParsedType::from_type(&field.ty)?;
+
+        let record_field = AstRecordField {
+            name,
+            export_name: field_attrs.rename.map(|rename| rename.value()),
+            ty,
+            docs: docs::collect_docs(&field.attrs),
+        };
+        record_fields.push(record_field);
+    }
+
+    Ok((record_fields, skipped_fields))
 }
 
 /// Check that record fields satisfy the following requirements:
 ///  - all fields must be public
-///  - field must have only doc attributes
+///  - field must have only doc attributes besides #[marine] ones removed before
 fn check_field(field: &syn::Field) -> Result<()> {
     match field.vis {
         syn::Visibility::Public(_) => {}
@@ -0,0 +1,171 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::syn_error;
+
+use syn::parse::Parse;
+use syn::parse::ParseStream;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::Result;
+
+/// Arguments of the #[marine] attribute, f.e. `#[marine(name = "get-user")]`.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct MacroAttrs {
+    /// Name of a function or a record in the module interface, the Rust name is used if it's None.
+    pub(crate) export_name: Option<syn::LitStr>,
+}
+
+impl Parse for MacroAttrs {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let args = Punctuated::<syn::MetaNameValue, syn::Token![,]>::parse_terminated(input)?;
+
+        let mut attrs = MacroAttrs::default();
+        for arg in args {
+            if !arg.path.is_ident("name") {
+                return syn_error!(
+                    arg.path.span(),
+                    "unknown #[marine] argument, only name is supported"
+                );
+            }
+            if attrs.export_name.is_some() {
+                return syn_error!(arg.span(), "name could be specified only once");
+            }
+
+            let export_name = match arg.lit {
+                syn::Lit::Str(export_name) => export_name,
+                lit => return syn_error!(lit.span(), "name should be a string literal"),
+            };
+            check_export_name(&export_name)?;
+
+            attrs.export_name = Some(export_name);
+        }
+
+        Ok(attrs)
+    }
+}
+
+/// Arguments of the #[marine] attribute applied to a record field.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct FieldAttrs {
+    /// Name of the field in the module interface, f.e. `#[marine(rename = "user-id")]`.
+    pub(crate) rename: Option<syn::LitStr>,
+
+    /// The field is excluded from the interface and set to `Default::default()` on lifting.
+    pub(crate) skip: bool,
+
+    /// The field is excluded from the interface and set to a value returned by the function
+    /// with the given path on lifting, f.e. `#[marine(default = "default_timeout")]`.
+    pub(crate) default: Option<syn::ExprPath>,
+}
+
+impl FieldAttrs {
+    pub(crate) fn is_skipped(&self) -> bool {
+        self.skip || self.default.is_some()
+    }
+}
+
+/// Parses and removes #[marine] attributes of a record field, they must be removed because
+/// attribute macros couldn't be applied to fields.
+pub(crate) fn take_field_attrs(field: &mut syn::Field) -> Result<FieldAttrs> {
+    let mut field_attrs = FieldAttrs::default();
+    let mut other_attrs = Vec::with_capacity(field.attrs.len());
+
+    for attr in field.attrs.drain(..) {
+        if !attr.path.is_ident("marine") {
+            other_attrs.push(attr);
+            continue;
+        }
+
+        let args = match attr.parse_meta()? {
+            syn::Meta::List(list) => list.nested,
+            meta => return syn_error!(meta.span(), "expected #[marine(...)]"),
+        };
+
+        for arg in args {
+            parse_field_arg(arg, &mut field_attrs)?;
+        }
+    }
+
+    field.attrs = other_attrs;
+
+    if let (Some(rename), true) = (&field_attrs.rename, field_attrs.is_skipped()) {
+        return syn_error!(rename.span(), "skipped field couldn't be renamed");
+    }
+
+    Ok(field_attrs)
+}
+
+fn parse_field_arg(arg: syn::NestedMeta, field_attrs: &mut FieldAttrs) -> Result<()> {
+    use syn::Meta;
+    use syn::NestedMeta;
+
+    match arg {
+        NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip") => {
+            field_attrs.skip = true;
+        }
+        NestedMeta::Meta(Meta::NameValue(arg)) if arg.path.is_ident("rename") => {
+            let rename = match arg.lit {
+                syn::Lit::Str(rename) => rename,
+                lit => return syn_error!(lit.span(), "rename should be a string literal"),
+            };
+            check_export_name(&rename)?;
+
+            field_attrs.rename = Some(rename);
+        }
+        NestedMeta::Meta(Meta::NameValue(arg)) if arg.path.is_ident("default") => {
+            let default = match arg.lit {
+                syn::Lit::Str(default) => default.parse::<syn::ExprPath>()?,
+                lit => {
+                    return syn_error!(
+                        lit.span(),
+                        "default should be a string with a function path"
+                    )
+                }
+            };
+
+            field_attrs.default = Some(default);
+        }
+        arg => {
+            return syn_error!(
+                arg.span(),
+                "unknown #[marine] field argument, only rename, skip and default are supported"
+            )
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that a name could be used in the module interface: it should start with a letter
+/// or an underscore and contain only ASCII letters, digits, underscores and hyphens.
+fn check_export_name(export_name: &syn::LitStr) -> Result<()> {
+    let name = export_name.value();
+    let mut chars = name.chars();
+
+    let is_first_char_valid =
+        matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_');
+    let is_rest_valid = chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+
+    if !is_first_char_valid || !is_rest_valid {
+        return syn_error!(
+            export_name.span(),
+            "name should start with a letter or '_' and contain only letters, digits, '_' and '-'"
+        );
+    }
+
+    Ok(())
+}
@@ -32,6 +32,7 @@ pub(crate) use foreign_mod_epilog::*;
 pub(crate) use utils::*;
 pub(crate) use vector_ser_der::*;
 
+use marine_alloc_elem_type::AllocElemType;
 use serde::Serialize;
 use serde::Deserialize;
 use syn::parse::Error;
@@ -144,6 +145,30 @@ impl ParsedType {
             | ParsedType::Record(..) => true,
         }
     }
+
+    /// Returns an element type that should be used to allocate a vector of this type
+    /// or None if such vector couldn't be allocated by `allocate` directly.
+    pub fn alloc_elem_type(&self) -> Option<AllocElemType> {
+        let elem_ty = match self {
+            ParsedType::Boolean(_) => AllocElemType::Boolean,
+            ParsedType::U8(_) => AllocElemType::U8,
+            ParsedType::U16(_) => AllocElemType::U16,
+            ParsedType::U32(_) => AllocElemType::U32,
+            ParsedType::U64(_) => AllocElemType::U64,
+            ParsedType::I8(_) => AllocElemType::I8,
+            ParsedType::I16(_) => AllocElemType::I16,
+            ParsedType::I32(_) => AllocElemType::I32,
+            ParsedType::I64(_) => AllocElemType::I64,
+            ParsedType::F32(_) => AllocElemType::F32,
+            ParsedType::F64(_) => AllocElemType::F64,
+            ParsedType::Utf8Str(_)
+            | ParsedType::Utf8String(_)
+            | ParsedType::Vector(..)
+            | ParsedType::Record(..) => return None,
+        };
+
+        Some(elem_ty)
+    }
 }
 
 fn type_to_path_passing_style(input_type: &syn::Type) -> syn::Result<(&syn::Path, PassingStyle)> {
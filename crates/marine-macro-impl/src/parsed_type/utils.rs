@@ -37,3 +37,12 @@ pub(crate) fn passing_style_of(ty: &ParsedType) -> &PassingStyle {
         Record(_, passing_style) => passing_style,
     }
 }
+
+/// Returns a path to the element type that a host uses to allocate a vector of the given type
+/// or None if elements of such vectors are passed as pairs of u32.
+pub(crate) fn alloc_elem_type_tokens(ty: &ParsedType) -> Option<proc_macro2::TokenStream> {
+    let elem_ty = ty.alloc_elem_type()?;
+    let elem_ty = crate::new_ident!(format!("{:?}", elem_ty));
+
+    Some(quote::quote! { marine_rs_sdk::internal::AllocElemType::#elem_ty })
+}
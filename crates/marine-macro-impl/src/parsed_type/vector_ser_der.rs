@@ -31,10 +31,8 @@ pub(crate) fn generate_vector_ser(
         ParsedType::Boolean(_) => {
             quote! {
                 let converted_bool_vector: Vec<u8> = arg.into_iter().map(|v| *v as u8).collect::<_>();
-                let ptr = converted_bool_vector.as_ptr();
                 let len = converted_bool_vector.len();
-
-                marine_rs_sdk::internal::add_object_to_release(Box::new(converted_bool_vector));
+                let ptr = marine_rs_sdk::internal::retain_buffer(converted_bool_vector);
 
                 (ptr as _, len as _)
             }
@@ -75,8 +73,9 @@ pub(crate) fn generate_vector_der(
 
     let values_deserializer = match value_ty {
         ParsedType::Boolean(_) => {
+            let elem_ty = super::alloc_elem_type_tokens(value_ty);
             quote! {
-                let arg: Vec<u8> = Vec::from_raw_parts(offset as _, size as _, size as _);
+                let arg: Vec<u8> = marine_rs_sdk::internal::take_allocated_vec(#elem_ty, offset as _, size as _);
                 arg.into_iter().map(|v| v != 0).collect::<Vec<bool>>()
             }
         }
@@ -84,8 +83,9 @@ pub(crate) fn generate_vector_der(
         ParsedType::Vector(ty, _) => vector_der(arg_name, ty),
         ParsedType::Record(record_name, _) => record_der(record_name),
         _ => {
+            let elem_ty = super::alloc_elem_type_tokens(value_ty);
             quote! {
-                Vec::from_raw_parts(offset as _, size as _, size as _)
+                marine_rs_sdk::internal::take_allocated_vec(#elem_ty, offset as _, size as _)
             }
         }
     };
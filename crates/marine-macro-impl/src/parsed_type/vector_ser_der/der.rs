@@ -21,7 +21,7 @@ use quote::quote;
 pub(super) fn string_der() -> proc_macro2::TokenStream {
     quote! {
         let vec_passing_size = 2;
-        let mut arg: Vec<u32> = Vec::from_raw_parts(offset as _, (vec_passing_size * size) as _, (vec_passing_size * size) as _);
+        let mut arg: Vec<u32> = marine_rs_sdk::internal::take_allocated_vec(marine_rs_sdk::internal::AllocElemType::U32, offset as _, (vec_passing_size * size) as _);
         let mut arg = arg.into_iter();
         let mut result = Vec::with_capacity(arg.len() / 2);
 
@@ -46,7 +46,7 @@ pub(super) fn vector_der(arg_name: &str, ty: &ParsedType) -> proc_macro2::TokenS
         #inner_vector_deserializer
 
         let vec_passing_size = 2;
-        let mut arg: Vec<u32> = Vec::from_raw_parts(offset as _, (vec_passing_size * size) as _, (vec_passing_size * size) as _);
+        let mut arg: Vec<u32> = marine_rs_sdk::internal::take_allocated_vec(marine_rs_sdk::internal::AllocElemType::U32, offset as _, (vec_passing_size * size) as _);
         let mut result = Vec::with_capacity(arg.len());
 
         let mut arg = arg.into_iter();
@@ -65,7 +65,7 @@ pub(super) fn record_der(record_name: &str) -> proc_macro2::TokenStream {
     let record_name_ident = crate::new_ident!(record_name);
 
     quote! {
-        let mut arg: Vec<u32> = Vec::from_raw_parts(offset as _, size as _, size as _);
+        let mut arg: Vec<u32> = marine_rs_sdk::internal::take_allocated_vec(marine_rs_sdk::internal::AllocElemType::U32, offset as _, size as _);
         let mut result = Vec::with_capacity(arg.len());
 
         for offset in arg {
@@ -27,9 +27,8 @@ pub(super) fn string_ser() -> proc_macro2::TokenStream {
             result.push(value.len() as _);
         }
 
-        let result_ptr = result.as_ptr();
         let result_len = result.len() / 2;
-        marine_rs_sdk::internal::add_object_to_release(Box::new(result));
+        let result_ptr = marine_rs_sdk::internal::retain_buffer(result);
 
         (result_ptr as _, result_len as _)
     }
@@ -52,9 +51,8 @@ pub(super) fn vector_ser(arg_name: &str, ty: &ParsedType) -> proc_macro2::TokenS
             result.push(size as _);
         }
 
-        let result_ptr = result.as_ptr();
         let result_len = result.len() / 2;
-        marine_rs_sdk::internal::add_object_to_release(Box::new(result));
+        let result_ptr = marine_rs_sdk::internal::retain_buffer(result);
 
         (result_ptr as _, result_len as _)
     }
@@ -68,9 +66,8 @@ pub(super) fn record_ser() -> proc_macro2::TokenStream {
             result.push(value.__m_generated_serialize() as _);
         }
 
-        let result_ptr = result.as_ptr();
         let result_len = result.len();
-        marine_rs_sdk::internal::add_object_to_release(Box::new(result));
+        let result_ptr = marine_rs_sdk::internal::retain_buffer(result);
 
         (result_ptr as _, result_len as _)
     }
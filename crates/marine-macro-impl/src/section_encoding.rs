@@ -0,0 +1,242 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Encoding of `SDKAst` items embedded into custom sections of a module.
+//!
+//! By default items are serialized into JSON without any header, like previous versions
+//! of the sdk do. With the `compact_sections` feature items are encoded by CBOR into entries
+//! prefixed with a header:
+//!```ignore
+//! | magic (4 bytes) | version (1 byte) | encoding (1 byte) | payload size (4 bytes, LE) | payload |
+//!```
+//! Both JSON items and entries are self-delimited, so sections could be concatenated, f.e. by
+//! a linker that merges sections with the same name or by tooling that collects all items into
+//! a single section. `decode_section` understands any mix of them.
+
+use crate::export_ast_types::SDKAst;
+
+use std::fmt;
+
+/// Magic bytes that start each encoded entry, a JSON item can't start with them.
+pub const SECTION_ENTRY_MAGIC: [u8; 4] = *b"\0msa";
+
+/// Version of the `SDKAst` layout written into entries, it's increased on each breaking change
+/// of `SDKAst`.
+pub const SECTION_FORMAT_VERSION: u8 = 1;
+
+const ENTRY_HEADER_SIZE: usize = SECTION_ENTRY_MAGIC.len() + 1 + 1 + 4;
+
+/// Encoding of an item in a section.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SectionEncoding {
+    /// Plain JSON without the header, it's used by `#[marine]` by default.
+    Json,
+    /// Compact binary encoding, it's used by `#[marine]` with the `compact_sections` feature.
+    Cbor,
+}
+
+impl SectionEncoding {
+    /// Returns an encoding used by the `#[marine]` macro.
+    pub(crate) fn generated() -> Self {
+        if cfg!(feature = "compact_sections") {
+            Self::Cbor
+        } else {
+            Self::Json
+        }
+    }
+
+    fn id(self) -> u8 {
+        match self {
+            Self::Json => 0,
+            Self::Cbor => 1,
+        }
+    }
+
+    fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(Self::Json),
+            1 => Some(Self::Cbor),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SectionDecodeError {
+    /// Section ends in the middle of an entry.
+    UnexpectedEnd {
+        offset: usize,
+    },
+
+    /// Entry was written by a newer sdk than this decoder supports.
+    UnsupportedVersion {
+        offset: usize,
+        version: u8,
+    },
+
+    UnknownEncoding {
+        offset: usize,
+        encoding: u8,
+    },
+
+    /// Data at the offset is neither an encoded entry nor a JSON item.
+    UnknownEntry {
+        offset: usize,
+    },
+
+    JsonError {
+        offset: usize,
+        error: serde_json::Error,
+    },
+
+    CborError {
+        offset: usize,
+        error: serde_cbor::Error,
+    },
+}
+
+/// Encodes an item, JSON items are written as is and CBOR ones into an entry with the header.
+pub fn encode_sdk_ast(ast: &SDKAst, encoding: SectionEncoding) -> Vec<u8> {
+    // serialization of SDKAst is infallible, because it consists of strings, vectors and enums
+    let payload = match encoding {
+        SectionEncoding::Json => return serde_json::to_vec(ast).unwrap(),
+        SectionEncoding::Cbor => serde_cbor::to_vec(ast).unwrap(),
+    };
+
+    let mut entry = Vec::with_capacity(ENTRY_HEADER_SIZE + payload.len());
+    entry.extend(&SECTION_ENTRY_MAGIC);
+    entry.push(SECTION_FORMAT_VERSION);
+    entry.push(encoding.id());
+    entry.extend(&(payload.len() as u32).to_le_bytes());
+    entry.extend(payload);
+
+    entry
+}
+
+/// Decodes all items from a section, the section could contain entries with the header,
+/// JSON items without it or a mix of them.
+pub fn decode_section(section: &[u8]) -> Result<Vec<SDKAst>, SectionDecodeError> {
+    let mut items = Vec::new();
+    let mut offset = 0;
+
+    while offset < section.len() {
+        let rest = &section[offset..];
+        let (item, entry_size) = if rest.starts_with(&SECTION_ENTRY_MAGIC) {
+            decode_entry(rest, offset)?
+        } else if rest[0] == b'{' {
+            decode_json_item(rest, offset)?
+        } else {
+            return Err(SectionDecodeError::UnknownEntry { offset });
+        };
+
+        items.push(item);
+        offset += entry_size;
+    }
+
+    Ok(items)
+}
+
+/// Merges several sections into one, all items are re-encoded with the given encoding.
+pub fn merge_sections<'s>(
+    sections: impl IntoIterator<Item = &'s [u8]>,
+    encoding: SectionEncoding,
+) -> Result<Vec<u8>, SectionDecodeError> {
+    let mut merged = Vec::new();
+    for section in sections {
+        for item in decode_section(section)? {
+            merged.extend(encode_sdk_ast(&item, encoding));
+        }
+    }
+
+    Ok(merged)
+}
+
+fn decode_entry(entry: &[u8], offset: usize) -> Result<(SDKAst, usize), SectionDecodeError> {
+    if entry.len() < ENTRY_HEADER_SIZE {
+        return Err(SectionDecodeError::UnexpectedEnd { offset });
+    }
+
+    let version = entry[SECTION_ENTRY_MAGIC.len()];
+    if version > SECTION_FORMAT_VERSION {
+        return Err(SectionDecodeError::UnsupportedVersion { offset, version });
+    }
+
+    let encoding_id = entry[SECTION_ENTRY_MAGIC.len() + 1];
+    let encoding =
+        SectionEncoding::from_id(encoding_id).ok_or(SectionDecodeError::UnknownEncoding {
+            offset,
+            encoding: encoding_id,
+        })?;
+
+    let size_offset = SECTION_ENTRY_MAGIC.len() + 2;
+    let payload_size = u32::from_le_bytes([
+        entry[size_offset],
+        entry[size_offset + 1],
+        entry[size_offset + 2],
+        entry[size_offset + 3],
+    ]) as usize;
+
+    let payload = entry
+        .get(ENTRY_HEADER_SIZE..ENTRY_HEADER_SIZE + payload_size)
+        .ok_or(SectionDecodeError::UnexpectedEnd { offset })?;
+    let item = match encoding {
+        SectionEncoding::Json => serde_json::from_slice(payload)
+            .map_err(|error| SectionDecodeError::JsonError { offset, error })?,
+        SectionEncoding::Cbor => serde_cbor::from_slice(payload)
+            .map_err(|error| SectionDecodeError::CborError { offset, error })?,
+    };
+
+    Ok((item, ENTRY_HEADER_SIZE + payload_size))
+}
+
+/// Decodes a JSON item written without the header.
+fn decode_json_item(data: &[u8], offset: usize) -> Result<(SDKAst, usize), SectionDecodeError> {
+    let mut items = serde_json::Deserializer::from_slice(data).into_iter::<SDKAst>();
+    let item = match items.next() {
+        Some(Ok(item)) => item,
+        Some(Err(error)) => return Err(SectionDecodeError::JsonError { offset, error }),
+        None => return Err(SectionDecodeError::UnexpectedEnd { offset }),
+    };
+
+    Ok((item, items.byte_offset()))
+}
+
+impl fmt::Display for SectionDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedEnd { offset } => {
+                write!(f, "section ends unexpectedly in the entry at {}", offset)
+            }
+            Self::UnsupportedVersion { offset, version } => write!(
+                f,
+                "entry at {} has version {}, but only versions up to {} are supported",
+                offset, version, SECTION_FORMAT_VERSION
+            ),
+            Self::UnknownEncoding { offset, encoding } => {
+                write!(f, "entry at {} has unknown encoding {}", offset, encoding)
+            }
+            Self::UnknownEntry { offset } => write!(f, "unknown data at {}", offset),
+            Self::JsonError { offset, error } => {
+                write!(f, "entry at {} isn't a valid JSON item: {}", offset, error)
+            }
+            Self::CborError { offset, error } => {
+                write!(f, "entry at {} isn't a valid CBOR item: {}", offset, error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SectionDecodeError {}
@@ -44,7 +44,7 @@ impl quote::ToTokens for ast_types::AstFn {
             signature.name
         ));
         let original_func_ident = new_ident!(signature.name);
-        let export_func_name = &signature.name;
+        let export_func_name = self.export_name();
 
         let FnPrologDescriptor {
             raw_arg_names,
@@ -78,6 +78,9 @@ impl quote::ToTokens for ast_types::AstFn {
             #[doc(hidden)]
             #[allow(clippy::all)]
             pub unsafe fn #func_name(#(#raw_arg_names: #raw_arg_types),*) #fn_return_type {
+                // release buffers retained for the host by the previous call
+                marine_rs_sdk::internal::reset_call_arena();
+
                 // arguments conversation from Wasm types to Rust types
                 #prolog
 
@@ -81,8 +81,7 @@ fn generate_serializer_fn(record: &AstRecord) -> proc_macro2::TokenStream {
 
             #serializer
 
-            let raw_record_ptr = raw_record.as_ptr();
-            marine_rs_sdk::internal::add_object_to_release(Box::new(raw_record));
+            let raw_record_ptr = marine_rs_sdk::internal::retain_buffer(raw_record);
 
             raw_record_ptr as _
         }
@@ -105,7 +104,7 @@ fn generate_deserializer_fn(record: &AstRecord) -> proc_macro2::TokenStream {
 
     quote::quote! {
         pub unsafe fn __m_generated_deserialize(record_ptr: *const u8) -> Self {
-            let raw_record: Vec<u8> = Vec::from_raw_parts(record_ptr as _, #record_size, #record_size);
+            let raw_record: Vec<u8> = marine_rs_sdk::internal::take_allocated_vec(marine_rs_sdk::internal::AllocElemType::U8, record_ptr as _, #record_size);
 
             #fields_der
 
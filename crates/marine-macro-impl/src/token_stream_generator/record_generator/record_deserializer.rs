@@ -35,16 +35,23 @@ pub(super) trait RecordDerGlueCodeGenerator {
 impl RecordDerGlueCodeGenerator for AstRecord {
     fn generate_der(&self) -> RecordDerDescriptor {
         match &self.fields {
-            AstRecordFields::Named(fields) => record_der_from_named(fields),
+            AstRecordFields::Named(fields) => record_der_from_named(fields, &self.skipped_fields),
             AstRecordFields::Unnamed(fields) => record_der_from_unnamed(fields),
             AstRecordFields::Unit => RecordDerDescriptor::default(),
         }
     }
 }
 
-fn record_der_from_named(fields: &[AstRecordField]) -> RecordDerDescriptor {
+fn record_der_from_named(
+    fields: &[AstRecordField],
+    skipped_fields: &[AstSkippedField],
+) -> RecordDerDescriptor {
     let builder = FieldValuesBuilder::build(fields.iter());
-    let record_ctor = field_ctors_from_named(fields.iter(), builder.field_value_idents.iter());
+    let record_ctor = field_ctors_from_named(
+        fields.iter(),
+        builder.field_value_idents.iter(),
+        skipped_fields,
+    );
 
     RecordDerDescriptor {
         fields_der: builder.fields_der,
@@ -65,19 +72,31 @@ fn record_der_from_unnamed(fields: &[AstRecordField]) -> RecordDerDescriptor {
 fn field_ctors_from_named<'a, 'v>(
     ast_fields: impl ExactSizeIterator<Item = &'a AstRecordField>,
     field_values: impl ExactSizeIterator<Item = &'v syn::Ident>,
+    skipped_fields: &[AstSkippedField],
 ) -> TokenStream {
-    let field_names = ast_fields
-        .map(|ast_field| {
-            new_ident!(ast_field
+    let field_ctors = ast_fields
+        .zip(field_values)
+        .map(|(ast_field, field_value)| {
+            let field_name = new_ident!(ast_field
                 .name
                 .as_ref()
-                .expect("all fields should have name"))
-        })
-        .collect::<Vec<_>>();
+                .expect("all fields should have name"));
+            quote! { #field_name: #field_value }
+        });
+
+    let skipped_field_ctors = skipped_fields.iter().map(|skipped_field| {
+        let field_name = new_ident!(skipped_field.name);
+        match &skipped_field.default {
+            Some(default) => quote! { #field_name: #default() },
+            None => quote! { #field_name: Default::default() },
+        }
+    });
+
+    let field_ctors = field_ctors.chain(skipped_field_ctors);
 
     quote! {
         Self {
-            #(#field_names: #field_values),*
+            #(#field_ctors),*
         }
     }
 }
@@ -25,9 +25,11 @@ macro_rules! new_ident {
 #[macro_export]
 macro_rules! prepare_global_data {
     ($mtype: ident, $self: ident, $name: expr, $data: ident, $data_size: ident, $global_static_name: ident, $section_name: ident) => {
-        // TODO: change serialization protocol
         let mtype = crate::export_ast_types::SDKAst::$mtype($self.clone().into());
-        let $data = serde_json::to_vec(&mtype).unwrap();
+        let $data = crate::section_encoding::encode_sdk_ast(
+            &mtype,
+            crate::section_encoding::SectionEncoding::generated(),
+        );
         let $data_size = $data.len();
         let $data = syn::LitByteStr::new(&$data, proc_macro2::Span::call_site());
 
@@ -60,13 +62,11 @@ pub fn get_record_size<'a>(
     let mut size = 0;
 
     for field in fields {
-        size += match field {
-            ParsedType::U8(_) | ParsedType::I8(_) | ParsedType::Boolean(_) => 1,
-            ParsedType::U16(_) | ParsedType::I16(_) => 2,
-            ParsedType::U32(_) | ParsedType::I32(_) | ParsedType::F32(_) => 4,
-            ParsedType::U64(_) | ParsedType::I64(_) | ParsedType::F64(_) => 8,
-            ParsedType::Record(..) => 4,
-            ParsedType::Vector(..) | ParsedType::Utf8Str(_) | ParsedType::Utf8String(_) => 2 * 4,
+        // scalar fields have the same size as elements of vectors allocated for them
+        size += match (field.alloc_elem_type(), field) {
+            (Some(elem_ty), _) => elem_ty.size(),
+            (None, ParsedType::Record(..)) => 4,
+            (None, _) => 2 * 4,
         };
     }
 
@@ -7,6 +7,7 @@ pub fn inner_arrays_1(arg: Vec<Vec<Vec<Vec<u8>>>>) -> Vec<Vec<Vec<Vec<u8>>>> {
 #[doc(hidden)]
 #[allow(clippy::all)]
 pub unsafe fn __m_generated_wrapper_func_inner_arrays_1(arg_0: u32, arg_1: u32) {
+    marine_rs_sdk::internal::reset_call_arena();
     unsafe fn __m_generated_vec_deserializer_0(offset: u32, size: u32) -> Vec<Vec<Vec<Vec<u8>>>> {
         unsafe fn __m_generated_vec_deserializer_0_Vec_Vec_u8__(
             offset: u32,
@@ -20,11 +21,11 @@ pub unsafe fn __m_generated_wrapper_func_inner_arrays_1(arg_0: u32, arg_1: u32)
                     offset: u32,
                     size: u32
                 ) -> Vec<u8> {
-                    Vec::from_raw_parts(offset as _, size as _, size as _)
+                    marine_rs_sdk::internal::take_allocated_vec(marine_rs_sdk::internal::AllocElemType::U8, offset as _, size as _)
                 }
                 let vec_passing_size = 2;
                 let mut arg: Vec<u32> =
-                    Vec::from_raw_parts(offset as _, (vec_passing_size * size) as _, (vec_passing_size * size) as _);
+                    marine_rs_sdk::internal::take_allocated_vec(marine_rs_sdk::internal::AllocElemType::U32, offset as _, (vec_passing_size * size) as _);
                 let mut result = Vec::with_capacity(arg.len());
                 let mut arg = arg.into_iter();
                 while let Some(offset) = arg.next() {
@@ -39,7 +40,7 @@ pub unsafe fn __m_generated_wrapper_func_inner_arrays_1(arg_0: u32, arg_1: u32)
             }
             let vec_passing_size = 2;
             let mut arg: Vec<u32> =
-                Vec::from_raw_parts(offset as _, (vec_passing_size * size) as _, (vec_passing_size * size) as _);
+                marine_rs_sdk::internal::take_allocated_vec(marine_rs_sdk::internal::AllocElemType::U32, offset as _, (vec_passing_size * size) as _);
             let mut result = Vec::with_capacity(arg.len());
             let mut arg = arg.into_iter();
             while let Some(offset) = arg.next() {
@@ -51,7 +52,7 @@ pub unsafe fn __m_generated_wrapper_func_inner_arrays_1(arg_0: u32, arg_1: u32)
             result
         }
         let vec_passing_size = 2;
-        let mut arg: Vec<u32> = Vec::from_raw_parts(offset as _, (vec_passing_size * size) as _, (vec_passing_size * size) as _);
+        let mut arg: Vec<u32> = marine_rs_sdk::internal::take_allocated_vec(marine_rs_sdk::internal::AllocElemType::U32, offset as _, (vec_passing_size * size) as _);
         let mut result = Vec::with_capacity(arg.len());
         let mut arg = arg.into_iter();
         while let Some(offset) = arg.next() {
@@ -82,9 +83,8 @@ pub unsafe fn __m_generated_wrapper_func_inner_arrays_1(arg_0: u32, arg_1: u32)
                     result.push(ptr as _);
                     result.push(size as _);
                 }
-                let result_ptr = result.as_ptr();
                 let result_len = result.len() / 2;
-                marine_rs_sdk::internal::add_object_to_release(Box::new(result));
+                let result_ptr = marine_rs_sdk::internal::retain_buffer(result);
                 (result_ptr as _, result_len as _)
             }
             let mut result: Vec<u32> = Vec::with_capacity(2 * arg.len());
@@ -93,9 +93,8 @@ pub unsafe fn __m_generated_wrapper_func_inner_arrays_1(arg_0: u32, arg_1: u32)
                 result.push(ptr as _);
                 result.push(size as _);
             }
-            let result_ptr = result.as_ptr();
             let result_len = result.len() / 2;
-            marine_rs_sdk::internal::add_object_to_release(Box::new(result));
+            let result_ptr = marine_rs_sdk::internal::retain_buffer(result);
             (result_ptr as _, result_len as _)
         }
         let mut result: Vec<u32> = Vec::with_capacity(2 * arg.len());
@@ -104,9 +103,8 @@ pub unsafe fn __m_generated_wrapper_func_inner_arrays_1(arg_0: u32, arg_1: u32)
             result.push(ptr as _);
             result.push(size as _);
         }
-        let result_ptr = result.as_ptr();
         let result_len = result.len() / 2;
-        marine_rs_sdk::internal::add_object_to_release(Box::new(result));
+        let result_ptr = marine_rs_sdk::internal::retain_buffer(result);
         (result_ptr as _, result_len as _)
     }
     {
@@ -35,6 +35,7 @@ pub unsafe fn __m_generated_wrapper_func_all_types(
     arg_12: u32,
     arg_13: u32
 ) {
+    marine_rs_sdk::internal::reset_call_arena();
     let converted_arg_0 = arg_0 as _;
     let converted_arg_1 = arg_1 as _;
     let converted_arg_2 = arg_2 as _;
@@ -47,7 +48,7 @@ pub unsafe fn __m_generated_wrapper_func_all_types(
     let converted_arg_9 = arg_9 as _;
     let converted_arg_10 = String::from_raw_parts(arg_10 as _, arg_11 as _, arg_11 as _);
     unsafe fn __m_generated_vec_deserializer_12(offset: u32, size: u32) -> Vec<u8> {
-        Vec::from_raw_parts(offset as _, size as _, size as _)
+        marine_rs_sdk::internal::take_allocated_vec(marine_rs_sdk::internal::AllocElemType::U8, offset as _, size as _)
     }
     let converted_arg_12 = __m_generated_vec_deserializer_12(arg_12 as _, arg_13 as _);
     let result = all_types(
@@ -0,0 +1,31 @@
+/// Greets a user.
+///
+/// # Arguments
+///
+/// * `name` - name of the user,
+///   it's put into the greeting as is
+/// * `times` - how many times to repeat the greeting
+pub fn greeting(name: String, times: u32) -> String {
+    name.repeat(times as _)
+}
+#[cfg(target_arch = "wasm32")]
+#[export_name = "greeting"]
+#[no_mangle]
+#[doc(hidden)]
+#[allow(clippy::all)]
+pub unsafe fn __m_generated_wrapper_func_greeting(arg_0: u32, arg_1: u32, arg_2: u32) {
+    marine_rs_sdk::internal::reset_call_arena();
+    let converted_arg_0 = String::from_raw_parts(arg_0 as _, arg_1 as _, arg_1 as _);
+    let converted_arg_2 = arg_2 as _;
+    let result = greeting(converted_arg_0, converted_arg_2);
+    marine_rs_sdk::internal::set_result_ptr(result.as_ptr() as _);
+    marine_rs_sdk::internal::set_result_size(result.len() as _);
+    marine_rs_sdk::internal::add_object_to_release(Box::new(result));
+}
+#[cfg(target_arch = "wasm32")]
+#[doc(hidden)]
+#[allow(clippy::all)]
+#[link_section = "__m_generated_section__greeting"]
+pub static __m_generated_static_global_greeting: [u8; 462usize] = {
+    * b"{\"ast_type\":\"Function\",\"signature\":{\"name\":\"greeting\",\"arguments\":[{\"name\":\"name\",\"ty\":{\"Utf8String\":\"ByValue\"},\"docs\":\"name of the user, it's put into the greeting as is\"},{\"name\":\"times\",\"ty\":{\"U32\":\"ByValue\"},\"docs\":\"how many times to repeat the greeting\"}],\"output_types\":[{\"Utf8String\":\"ByValue\"}],\"docs\":\"Greets a user.\\n\\n# Arguments\\n\\n* `name` - name of the user,\\n  it's put into the greeting as is\\n* `times` - how many times to repeat the greeting\"}}"
+};
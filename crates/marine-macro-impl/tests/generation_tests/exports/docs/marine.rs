@@ -0,0 +1,10 @@
+/// Greets a user.
+///
+/// # Arguments
+///
+/// * `name` - name of the user,
+///   it's put into the greeting as is
+/// * `times` - how many times to repeat the greeting
+pub fn greeting(name: String, times: u32) -> String {
+    name.repeat(times as _)
+}
@@ -7,11 +7,12 @@ pub fn test_array_refs(arg: &Vec<Vec<String>>) -> &Vec<Vec<Vec<Vec<String>>>> {
 #[doc(hidden)]
 #[allow(clippy::all)]
 pub unsafe fn __m_generated_wrapper_func_test_array_refs(arg_0: u32, arg_1: u32) {
+    marine_rs_sdk::internal::reset_call_arena();
     unsafe fn __m_generated_vec_deserializer_0(offset: u32, size: u32) -> Vec<Vec<String>> {
         unsafe fn __m_generated_vec_deserializer_0_String(offset: u32, size: u32) -> Vec<String> {
             let vec_passing_size = 2;
             let mut arg: Vec<u32> =
-                Vec::from_raw_parts(offset as _, (vec_passing_size * size) as _, (vec_passing_size * size) as _);
+                marine_rs_sdk::internal::take_allocated_vec(marine_rs_sdk::internal::AllocElemType::U32, offset as _, (vec_passing_size * size) as _);
             let mut arg = arg.into_iter();
             let mut result = Vec::with_capacity(arg.len() / 2);
             while let Some(offset) = arg.next() {
@@ -22,7 +23,7 @@ pub unsafe fn __m_generated_wrapper_func_test_array_refs(arg_0: u32, arg_1: u32)
             result
         }
         let vec_passing_size = 2;
-        let mut arg: Vec<u32> = Vec::from_raw_parts(offset as _, (vec_passing_size * size) as _, (vec_passing_size * size) as _);
+        let mut arg: Vec<u32> = marine_rs_sdk::internal::take_allocated_vec(marine_rs_sdk::internal::AllocElemType::U32, offset as _, (vec_passing_size * size) as _);
         let mut result = Vec::with_capacity(arg.len());
         let mut arg = arg.into_iter();
         while let Some(offset) = arg.next() {
@@ -49,9 +50,8 @@ pub unsafe fn __m_generated_wrapper_func_test_array_refs(arg_0: u32, arg_1: u32)
                         result.push(value.as_ptr() as _);
                         result.push(value.len() as _);
                     }
-                    let result_ptr = result.as_ptr();
                     let result_len = result.len() / 2;
-                    marine_rs_sdk::internal::add_object_to_release(Box::new(result));
+                    let result_ptr = marine_rs_sdk::internal::retain_buffer(result);
                     (result_ptr as _, result_len as _)
                 }
                 let mut result: Vec<u32> = Vec::with_capacity(2 * arg.len());
@@ -61,9 +61,8 @@ pub unsafe fn __m_generated_wrapper_func_test_array_refs(arg_0: u32, arg_1: u32)
                     result.push(ptr as _);
                     result.push(size as _);
                 }
-                let result_ptr = result.as_ptr();
                 let result_len = result.len() / 2;
-                marine_rs_sdk::internal::add_object_to_release(Box::new(result));
+                let result_ptr = marine_rs_sdk::internal::retain_buffer(result);
                 (result_ptr as _, result_len as _)
             }
             let mut result: Vec<u32> = Vec::with_capacity(2 * arg.len());
@@ -73,9 +72,8 @@ pub unsafe fn __m_generated_wrapper_func_test_array_refs(arg_0: u32, arg_1: u32)
                 result.push(ptr as _);
                 result.push(size as _);
             }
-            let result_ptr = result.as_ptr();
             let result_len = result.len() / 2;
-            marine_rs_sdk::internal::add_object_to_release(Box::new(result));
+            let result_ptr = marine_rs_sdk::internal::retain_buffer(result);
             (result_ptr as _, result_len as _)
         }
         let mut result: Vec<u32> = Vec::with_capacity(2 * arg.len());
@@ -84,9 +82,8 @@ pub unsafe fn __m_generated_wrapper_func_test_array_refs(arg_0: u32, arg_1: u32)
             result.push(ptr as _);
             result.push(size as _);
         }
-        let result_ptr = result.as_ptr();
         let result_len = result.len() / 2;
-        marine_rs_sdk::internal::add_object_to_release(Box::new(result));
+        let result_ptr = marine_rs_sdk::internal::retain_buffer(result);
         (result_ptr as _, result_len as _)
     }
     {
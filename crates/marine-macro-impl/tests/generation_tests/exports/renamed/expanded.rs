@@ -0,0 +1,23 @@
+pub fn get_user_v2(name: String) -> String {
+    name
+}
+#[cfg(target_arch = "wasm32")]
+#[export_name = "get-user"]
+#[no_mangle]
+#[doc(hidden)]
+#[allow(clippy::all)]
+pub unsafe fn __m_generated_wrapper_func_get_user_v2(arg_0: u32, arg_1: u32) {
+    marine_rs_sdk::internal::reset_call_arena();
+    let converted_arg_0 = String::from_raw_parts(arg_0 as _, arg_1 as _, arg_1 as _);
+    let result = get_user_v2(converted_arg_0);
+    marine_rs_sdk::internal::set_result_ptr(result.as_ptr() as _);
+    marine_rs_sdk::internal::set_result_size(result.len() as _);
+    marine_rs_sdk::internal::add_object_to_release(Box::new(result));
+}
+#[cfg(target_arch = "wasm32")]
+#[doc(hidden)]
+#[allow(clippy::all)]
+#[link_section = "__m_generated_section__get_user_v2"]
+pub static __m_generated_static_global_get_user_v2: [u8; 157usize] = {
+    * b"{\"ast_type\":\"Function\",\"signature\":{\"name\":\"get-user\",\"arguments\":[{\"name\":\"name\",\"ty\":{\"Utf8String\":\"ByValue\"}}],\"output_types\":[{\"Utf8String\":\"ByValue\"}]}}"
+};
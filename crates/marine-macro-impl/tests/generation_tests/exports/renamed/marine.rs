@@ -0,0 +1,3 @@
+pub fn get_user_v2(name: String) -> String {
+    name
+}
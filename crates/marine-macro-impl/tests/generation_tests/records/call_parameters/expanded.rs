@@ -43,9 +43,8 @@ impl CallParameters {
                 for value in arg {
                     result.push(value.__m_generated_serialize() as _);
                 }
-                let result_ptr = result.as_ptr();
                 let result_len = result.len();
-                marine_rs_sdk::internal::add_object_to_release(Box::new(result));
+                let result_ptr = marine_rs_sdk::internal::retain_buffer(result);
                 (result_ptr as _, result_len as _)
             }
             let mut result: Vec<u32> = Vec::with_capacity(2 * arg.len());
@@ -55,21 +54,19 @@ impl CallParameters {
                 result.push(ptr as _);
                 result.push(size as _);
             }
-            let result_ptr = result.as_ptr();
             let result_len = result.len() / 2;
-            marine_rs_sdk::internal::add_object_to_release(Box::new(result));
+            let result_ptr = marine_rs_sdk::internal::retain_buffer(result);
             (result_ptr as _, result_len as _)
         }
         let serialized_arg_5 =
             unsafe { __m_generated_vec_serializer_tetraplets_5(&self.tetraplets) };
         raw_record.extend(&serialized_arg_5.0.to_le_bytes());
         raw_record.extend(&serialized_arg_5.1.to_le_bytes());
-        let raw_record_ptr = raw_record.as_ptr();
-        marine_rs_sdk::internal::add_object_to_release(Box::new(raw_record));
+        let raw_record_ptr = marine_rs_sdk::internal::retain_buffer(raw_record);
         raw_record_ptr as _
     }
     pub unsafe fn __m_generated_deserialize(record_ptr: *const u8) -> Self {
-        let raw_record: Vec<u8> = Vec::from_raw_parts(record_ptr as _, 48usize, 48usize);
+        let raw_record: Vec<u8> = marine_rs_sdk::internal::take_allocated_vec(marine_rs_sdk::internal::AllocElemType::U8, record_ptr as _, 48usize);
         let field_0 = unsafe {
             let offset = u32::from_le_bytes([
                 raw_record[0usize],
@@ -153,7 +150,7 @@ impl CallParameters {
                 offset: u32,
                 size: u32
             ) -> Vec<SecurityTetraplet> {
-                let mut arg: Vec<u32> = Vec::from_raw_parts(offset as _, size as _, size as _);
+                let mut arg: Vec<u32> = marine_rs_sdk::internal::take_allocated_vec(marine_rs_sdk::internal::AllocElemType::U32, offset as _, size as _);
                 let mut result = Vec::with_capacity(arg.len());
                 for offset in arg {
                     let value = SecurityTetraplet::__m_generated_deserialize(offset as _);
@@ -163,7 +160,7 @@ impl CallParameters {
             }
             let vec_passing_size = 2;
             let mut arg: Vec<u32> =
-                Vec::from_raw_parts(offset as _, (vec_passing_size * size) as _, (vec_passing_size * size) as _);
+                marine_rs_sdk::internal::take_allocated_vec(marine_rs_sdk::internal::AllocElemType::U32, offset as _, (vec_passing_size * size) as _);
             let mut result = Vec::with_capacity(arg.len());
             let mut arg = arg.into_iter();
             while let Some(offset) = arg.next() {
@@ -203,6 +200,6 @@ impl CallParameters {
 #[doc(hidden)]
 #[allow(clippy::all)]
 #[link_section = "__m_generated_section__CallParameters"]
-pub static __m_generated_static_global_CallParameters: [u8; 455usize] = {
-    * b"{\"ast_type\":\"Record\",\"name\":\"CallParameters\",\"fields\":{\"Named\":[{\"name\":\"init_peer_id\",\"ty\":{\"Utf8String\":\"ByValue\"}},{\"name\":\"service_id\",\"ty\":{\"Utf8String\":\"ByValue\"}},{\"name\":\"service_creator_peer_id\",\"ty\":{\"Utf8String\":\"ByValue\"}},{\"name\":\"host_id\",\"ty\":{\"Utf8String\":\"ByValue\"}},{\"name\":\"particle_id\",\"ty\":{\"Utf8String\":\"ByValue\"}},{\"name\":\"tetraplets\",\"ty\":{\"Vector\":[{\"Vector\":[{\"Record\":[\"SecurityTetraplet\",\"ByValue\"]},\"ByValue\"]},\"ByValue\"]}}]}}"
+pub static __m_generated_static_global_CallParameters: [u8; 765usize] = {
+    * b"{\"ast_type\":\"Record\",\"name\":\"CallParameters\",\"fields\":{\"Named\":[{\"name\":\"init_peer_id\",\"ty\":{\"Utf8String\":\"ByValue\"},\"docs\":\"Peer id of the AIR script initiator.\"},{\"name\":\"service_id\",\"ty\":{\"Utf8String\":\"ByValue\"},\"docs\":\"Id of the current service.\"},{\"name\":\"service_creator_peer_id\",\"ty\":{\"Utf8String\":\"ByValue\"},\"docs\":\"Id of the service creator.\"},{\"name\":\"host_id\",\"ty\":{\"Utf8String\":\"ByValue\"},\"docs\":\"Id of the host which run this service.\"},{\"name\":\"particle_id\",\"ty\":{\"Utf8String\":\"ByValue\"},\"docs\":\"Id of the particle which execution resulted a call this service.\"},{\"name\":\"tetraplets\",\"ty\":{\"Vector\":[{\"Vector\":[{\"Record\":[\"SecurityTetraplet\",\"ByValue\"]},\"ByValue\"]},\"ByValue\"]},\"docs\":\"Security tetraplets which described origin of the arguments.\"}]}}"
 };
@@ -0,0 +1,61 @@
+pub struct User {
+    pub id: u64,
+    pub name: String,
+    pub cache: std::collections::HashMap<String, String>,
+    timeout: u32,
+}
+#[cfg(target_arch = "wasm32")]
+#[doc(hidden)]
+#[allow(clippy::all)]
+impl User {
+    pub fn __m_generated_serialize(&self) -> *const u8 {
+        let mut raw_record: Vec<u8> = Vec::with_capacity(4 * 2usize);
+        raw_record.extend(&self.id.to_le_bytes());
+        let field_ident_ptr = self.name.as_ptr() as u32;
+        raw_record.extend(&field_ident_ptr.to_le_bytes());
+        raw_record.extend(&(self.name.len() as u32).to_le_bytes());
+        let raw_record_ptr = marine_rs_sdk::internal::retain_buffer(raw_record);
+        raw_record_ptr as _
+    }
+    pub unsafe fn __m_generated_deserialize(record_ptr: *const u8) -> Self {
+        let raw_record: Vec<u8> = marine_rs_sdk::internal::take_allocated_vec(marine_rs_sdk::internal::AllocElemType::U8, record_ptr as _, 16usize);
+        let field_0 = u64::from_le_bytes([
+            raw_record[0usize],
+            raw_record[0usize + 1],
+            raw_record[0usize + 2],
+            raw_record[0usize + 3],
+            raw_record[0usize + 4],
+            raw_record[0usize + 5],
+            raw_record[0usize + 6],
+            raw_record[0usize + 7],
+        ]);
+        let field_1 = unsafe {
+            let offset = u32::from_le_bytes([
+                raw_record[8usize],
+                raw_record[8usize + 1],
+                raw_record[8usize + 2],
+                raw_record[8usize + 3],
+            ]);
+            let size = u32::from_le_bytes([
+                raw_record[8usize + 4],
+                raw_record[8usize + 5],
+                raw_record[8usize + 6],
+                raw_record[8usize + 7],
+            ]);
+            String::from_raw_parts(offset as _, size as _, size as _)
+        };
+        Self {
+            id: field_0,
+            name: field_1,
+            cache: Default::default(),
+            timeout: default_timeout()
+        }
+    }
+}
+#[cfg(target_arch = "wasm32")]
+#[doc(hidden)]
+#[allow(clippy::all)]
+#[link_section = "__m_generated_section__User"]
+pub static __m_generated_static_global_User: [u8; 144usize] = {
+    * b"{\"ast_type\":\"Record\",\"name\":\"User\",\"fields\":{\"Named\":[{\"name\":\"user-id\",\"ty\":{\"U64\":\"ByValue\"}},{\"name\":\"name\",\"ty\":{\"Utf8String\":\"ByValue\"}}]}}"
+};
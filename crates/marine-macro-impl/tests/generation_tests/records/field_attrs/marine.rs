@@ -0,0 +1,12 @@
+pub struct User {
+    #[marine(rename = "user-id")]
+    pub id: u64,
+
+    pub name: String,
+
+    #[marine(skip)]
+    pub cache: std::collections::HashMap<String, String>,
+
+    #[marine(default = "default_timeout")]
+    timeout: u32,
+}
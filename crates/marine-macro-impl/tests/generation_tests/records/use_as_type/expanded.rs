@@ -7,6 +7,7 @@ pub fn inner_arrays_2(arg: Vec<Vec<Vec<Vec<TestRecord>>>>) -> Vec<Vec<Vec<Vec<Te
 #[doc(hidden)]
 #[allow(clippy::all)]
 pub unsafe fn __m_generated_wrapper_func_inner_arrays_2(arg_0: u32, arg_1: u32) {
+    marine_rs_sdk::internal::reset_call_arena();
     unsafe fn __m_generated_vec_deserializer_0(
         offset: u32,
         size: u32
@@ -23,7 +24,7 @@ pub unsafe fn __m_generated_wrapper_func_inner_arrays_2(arg_0: u32, arg_1: u32)
                     offset: u32,
                     size: u32
                 ) -> Vec<TestRecord> {
-                    let mut arg: Vec<u32> = Vec::from_raw_parts(offset as _, size as _, size as _);
+                    let mut arg: Vec<u32> = marine_rs_sdk::internal::take_allocated_vec(marine_rs_sdk::internal::AllocElemType::U32, offset as _, size as _);
                     let mut result = Vec::with_capacity(arg.len());
                     for offset in arg {
                         let value = TestRecord::__m_generated_deserialize(offset as _);
@@ -33,7 +34,7 @@ pub unsafe fn __m_generated_wrapper_func_inner_arrays_2(arg_0: u32, arg_1: u32)
                 }
                 let vec_passing_size = 2;
                 let mut arg: Vec<u32> =
-                    Vec::from_raw_parts(offset as _, (vec_passing_size * size) as _, (vec_passing_size * size) as _);
+                    marine_rs_sdk::internal::take_allocated_vec(marine_rs_sdk::internal::AllocElemType::U32, offset as _, (vec_passing_size * size) as _);
                 let mut result = Vec::with_capacity(arg.len());
                 let mut arg = arg.into_iter();
                 while let Some(offset) = arg.next() {
@@ -45,7 +46,7 @@ pub unsafe fn __m_generated_wrapper_func_inner_arrays_2(arg_0: u32, arg_1: u32)
             }
             let vec_passing_size = 2;
             let mut arg: Vec<u32> =
-                Vec::from_raw_parts(offset as _, (vec_passing_size * size) as _, (vec_passing_size * size) as _);
+                marine_rs_sdk::internal::take_allocated_vec(marine_rs_sdk::internal::AllocElemType::U32, offset as _, (vec_passing_size * size) as _);
             let mut result = Vec::with_capacity(arg.len());
             let mut arg = arg.into_iter();
             while let Some(offset) = arg.next() {
@@ -59,7 +60,7 @@ pub unsafe fn __m_generated_wrapper_func_inner_arrays_2(arg_0: u32, arg_1: u32)
             result
         }
         let vec_passing_size = 2;
-        let mut arg: Vec<u32> = Vec::from_raw_parts(offset as _, (vec_passing_size * size) as _, (vec_passing_size * size) as _);
+        let mut arg: Vec<u32> = marine_rs_sdk::internal::take_allocated_vec(marine_rs_sdk::internal::AllocElemType::U32, offset as _, (vec_passing_size * size) as _);
         let mut result = Vec::with_capacity(arg.len());
         let mut arg = arg.into_iter();
         while let Some(offset) = arg.next() {
@@ -86,9 +87,8 @@ pub unsafe fn __m_generated_wrapper_func_inner_arrays_2(arg_0: u32, arg_1: u32)
                     for value in arg {
                         result.push(value.__m_generated_serialize() as _);
                     }
-                    let result_ptr = result.as_ptr();
                     let result_len = result.len();
-                    marine_rs_sdk::internal::add_object_to_release(Box::new(result));
+                    let result_ptr = marine_rs_sdk::internal::retain_buffer(result);
                     (result_ptr as _, result_len as _)
                 }
                 let mut result: Vec<u32> = Vec::with_capacity(2 * arg.len());
@@ -97,9 +97,8 @@ pub unsafe fn __m_generated_wrapper_func_inner_arrays_2(arg_0: u32, arg_1: u32)
                     result.push(ptr as _);
                     result.push(size as _);
                 }
-                let result_ptr = result.as_ptr();
                 let result_len = result.len() / 2;
-                marine_rs_sdk::internal::add_object_to_release(Box::new(result));
+                let result_ptr = marine_rs_sdk::internal::retain_buffer(result);
                 (result_ptr as _, result_len as _)
             }
             let mut result: Vec<u32> = Vec::with_capacity(2 * arg.len());
@@ -109,9 +108,8 @@ pub unsafe fn __m_generated_wrapper_func_inner_arrays_2(arg_0: u32, arg_1: u32)
                 result.push(ptr as _);
                 result.push(size as _);
             }
-            let result_ptr = result.as_ptr();
             let result_len = result.len() / 2;
-            marine_rs_sdk::internal::add_object_to_release(Box::new(result));
+            let result_ptr = marine_rs_sdk::internal::retain_buffer(result);
             (result_ptr as _, result_len as _)
         }
         let mut result: Vec<u32> = Vec::with_capacity(2 * arg.len());
@@ -120,9 +118,8 @@ pub unsafe fn __m_generated_wrapper_func_inner_arrays_2(arg_0: u32, arg_1: u32)
             result.push(ptr as _);
             result.push(size as _);
         }
-        let result_ptr = result.as_ptr();
         let result_len = result.len() / 2;
-        marine_rs_sdk::internal::add_object_to_release(Box::new(result));
+        let result_ptr = marine_rs_sdk::internal::retain_buffer(result);
         (result_ptr as _, result_len as _)
     }
     {
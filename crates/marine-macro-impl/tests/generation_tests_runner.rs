@@ -1,6 +1,10 @@
+// expanded files contain items encoded into JSON
+#![cfg(not(feature = "compact_sections"))]
+
 mod utils;
 
 use utils::test_marine_token_streams;
+use utils::test_marine_token_streams_with_attrs;
 
 #[test]
 fn exports_arrays() {
@@ -18,6 +22,14 @@ fn exports_basic_types() {
     ));
 }
 
+#[test]
+fn exports_docs() {
+    assert!(test_marine_token_streams(
+        "tests/generation_tests/exports/docs/marine.rs",
+        "tests/generation_tests/exports/docs/expanded.rs",
+    ));
+}
+
 #[test]
 fn exports_refs() {
     assert!(test_marine_token_streams(
@@ -26,6 +38,15 @@ fn exports_refs() {
     ));
 }
 
+#[test]
+fn exports_renamed() {
+    assert!(test_marine_token_streams_with_attrs(
+        r#"name = "get-user""#,
+        "tests/generation_tests/exports/renamed/marine.rs",
+        "tests/generation_tests/exports/renamed/expanded.rs",
+    ));
+}
+
 #[test]
 fn records_call_parameters() {
     assert!(test_marine_token_streams(
@@ -41,3 +62,11 @@ fn records_use_as_type() {
         "tests/generation_tests/records/use_as_type/expanded.rs",
     ));
 }
+
+#[test]
+fn records_field_attrs() {
+    assert!(test_marine_token_streams(
+        "tests/generation_tests/records/field_attrs/marine.rs",
+        "tests/generation_tests/records/field_attrs/expanded.rs",
+    ));
+}
@@ -0,0 +1,107 @@
+use marine_macro_impl::decode_section;
+use marine_macro_impl::encode_sdk_ast;
+use marine_macro_impl::merge_sections;
+use marine_macro_impl::SDKAst;
+use marine_macro_impl::SectionDecodeError;
+use marine_macro_impl::SectionEncoding;
+use marine_macro_impl::SECTION_FORMAT_VERSION;
+
+const LEGACY_FUNCTION: &str = r#"{"ast_type":"Function","signature":{"name":"greeting","arguments":[{"name":"name","ty":{"Utf8String":"ByValue"}}],"output_types":[{"Utf8String":"ByValue"}]}}"#;
+const LEGACY_RECORD: &str = r#"{"ast_type":"Record","name":"User","fields":{"Named":[{"name":"id","ty":{"U64":"ByValue"}}]}}"#;
+
+fn item_names(items: &[SDKAst]) -> Vec<String> {
+    items
+        .iter()
+        .map(|item| match item {
+            SDKAst::Function(function) => function.signature.name.clone(),
+            SDKAst::ExternMod(extern_mod) => extern_mod.namespace.clone(),
+            SDKAst::Record(record) => record.name.clone(),
+        })
+        .collect()
+}
+
+fn legacy_items() -> Vec<SDKAst> {
+    decode_section(format!("{}{}", LEGACY_FUNCTION, LEGACY_RECORD).as_bytes())
+        .expect("legacy items should be decoded")
+}
+
+#[test]
+fn legacy_json_items_decoded() {
+    let items = legacy_items();
+
+    assert_eq!(item_names(&items), vec!["greeting", "User"]);
+}
+
+#[test]
+fn encoded_entries_decoded() {
+    let items = legacy_items();
+
+    for encoding in [SectionEncoding::Json, SectionEncoding::Cbor].iter() {
+        let section = items
+            .iter()
+            .flat_map(|item| encode_sdk_ast(item, *encoding))
+            .collect::<Vec<_>>();
+        let decoded = decode_section(&section).expect("entries should be decoded");
+
+        assert_eq!(item_names(&decoded), vec!["greeting", "User"]);
+    }
+}
+
+#[test]
+fn json_items_written_without_header() {
+    let items = legacy_items();
+
+    let json_item = encode_sdk_ast(&items[0], SectionEncoding::Json);
+
+    assert_eq!(json_item, LEGACY_FUNCTION.as_bytes());
+}
+
+#[test]
+fn cbor_entries_are_more_compact() {
+    let items = legacy_items();
+
+    let json_entry = encode_sdk_ast(&items[0], SectionEncoding::Json);
+    let cbor_entry = encode_sdk_ast(&items[0], SectionEncoding::Cbor);
+
+    assert!(cbor_entry.len() < json_entry.len());
+}
+
+#[test]
+fn merged_sections_decoded() {
+    let items = legacy_items();
+    let cbor_section = encode_sdk_ast(&items[1], SectionEncoding::Cbor);
+
+    let merged = merge_sections(
+        vec![LEGACY_FUNCTION.as_bytes(), cbor_section.as_slice()],
+        SectionEncoding::Cbor,
+    )
+    .expect("sections should be merged");
+    let decoded = decode_section(&merged).expect("merged section should be decoded");
+
+    assert_eq!(item_names(&decoded), vec!["greeting", "User"]);
+}
+
+#[test]
+fn newer_version_rejected() {
+    let items = legacy_items();
+    let mut entry = encode_sdk_ast(&items[0], SectionEncoding::Cbor);
+    entry[4] = SECTION_FORMAT_VERSION + 1;
+
+    match decode_section(&entry) {
+        Err(SectionDecodeError::UnsupportedVersion { offset: 0, version }) => {
+            assert_eq!(version, SECTION_FORMAT_VERSION + 1)
+        }
+        result => panic!("expected UnsupportedVersion, got {:?}", result),
+    }
+}
+
+#[test]
+fn truncated_entry_rejected() {
+    let items = legacy_items();
+    let entry = encode_sdk_ast(&items[0], SectionEncoding::Cbor);
+
+    match decode_section(&entry[..entry.len() - 1]) {
+        Err(SectionDecodeError::UnexpectedEnd { offset: 0 }) => {}
+        result => panic!("expected UnexpectedEnd, got {:?}", result),
+    }
+}
@@ -26,9 +26,23 @@ where
     FP: AsRef<Path>,
     EP: AsRef<Path>,
 {
+    test_marine_token_streams_with_attrs("", marine_path, expanded_path)
+}
+
+/// Does the same as `test_marine_token_streams`, but applies the macro with the given arguments.
+pub fn test_marine_token_streams_with_attrs<FP, EP>(
+    attrs: &str,
+    marine_path: FP,
+    expanded_path: EP,
+) -> bool
+where
+    FP: AsRef<Path>,
+    EP: AsRef<Path>,
+{
+    let attrs: proc_macro2::TokenStream = attrs.parse().expect("attributes should be parsed");
     let marine_item = stream_from_file(marine_path);
     let test_token_stream = quote::quote! { #marine_item };
-    let marine_token_streams = marine(test_token_stream)
+    let marine_token_streams = marine(attrs, test_token_stream)
         .unwrap_or_else(|e| panic!("failed to apply the marine macro due {}", e));
 
     let expanded_item = items_from_file(expanded_path);
@@ -49,6 +49,53 @@
 //! }
 //!
 //! ```
+//!
+//! Functions and structs could be exported under a name that differs from the Rust one.
+//! Names must be unique across the module, tools generating definitions from the module
+//! interface report records sharing a name:
+//!
+//! ```ignore
+//! #[marine(name = "get-user")]
+//! pub fn get_user_v2(name: String) -> User {
+//!     User { name }
+//! }
+//!
+//! #[marine(name = "user-v2")]
+//! pub struct User {
+//!     pub name: String,
+//! }
+//! ```
+//!
+//! Record fields could be renamed in the interface or skipped from it, skipped fields are
+//! initialized on deserialization by `Default::default()` or by the provided function:
+//!
+//! ```ignore
+//! #[marine]
+//! pub struct User {
+//!     #[marine(rename = "user-id")]
+//!     pub id: u64,
+//!     #[marine(skip)]
+//!     pub cache: HashMap<String, String>,
+//!     #[marine(skip, default = "default_timeout")]
+//!     pub timeout: u32,
+//! }
+//! ```
+//!
+//! Doc comments of functions, records and record fields are embedded into the module
+//! alongside the interface, arguments are described in the `# Arguments` section of
+//! function docs:
+//!
+//! ```ignore
+//! /// Returns a user by its name.
+//! ///
+//! /// # Arguments
+//! ///
+//! /// * `name` - name of the user
+//! #[marine]
+//! pub fn get_user(name: String) -> User {
+//!     User { name }
+//! }
+//! ```
 
 #![doc(html_root_url = "https://docs.rs/marine-macro/0.6.10")]
 #![deny(
@@ -66,9 +113,9 @@ use marine_macro_impl::marine as marine_impl;
 use proc_macro::TokenStream;
 
 #[proc_macro_attribute]
-pub fn marine(_attr: TokenStream, input: TokenStream) -> TokenStream {
+pub fn marine(attr: TokenStream, input: TokenStream) -> TokenStream {
     // into converts proc_macro::TokenStream to proc_macro2::TokenStream
-    match marine_impl(input.into()) {
+    match marine_impl(attr.into(), input.into()) {
         Ok(v) => v,
         // converts syn:error to proc_macro2::TokenStream
         Err(e) => e.to_compile_error(),
@@ -80,9 +127,9 @@ pub fn marine(_attr: TokenStream, input: TokenStream) -> TokenStream {
 // deprecated macro for backwards compatibility
 #[deprecated(since = "0.6.2", note = "please use the #[marine] macro instead")]
 #[proc_macro_attribute]
-pub fn fce(_attr: TokenStream, input: TokenStream) -> TokenStream {
+pub fn fce(attr: TokenStream, input: TokenStream) -> TokenStream {
     // into converts proc_macro::TokenStream to proc_macro2::TokenStream
-    match marine_impl(input.into()) {
+    match marine_impl(attr.into(), input.into()) {
         Ok(v) => v,
         // converts syn:error to proc_macro2::TokenStream
         Err(e) => e.to_compile_error(),
@@ -15,14 +15,110 @@
  */
 
 use darling::FromMeta;
+use darling::util::PathList;
+
+use std::collections::HashMap;
 
 /// Describes attributes of `marine_test` macro.
 #[derive(Debug, Default, Clone, FromMeta)]
 pub(crate) struct MTestAttributes {
     /// Path to a config file of a tested service.
+    #[darling(default)]
+    pub(crate) config_path: Option<String>,
+
+    /// Path to compiled modules of a service.
+    #[darling(default)]
+    pub(crate) modules_dir: Option<String>,
+
+    /// Mocks of host imports: a module name mapped to import names and Rust expressions
+    /// evaluating to closures or functions that replace these imports.
+    #[darling(default)]
+    pub(crate) host_imports: HostImportMocks,
+
+    /// Namespace mocked host imports are looked up in, `host` by default. It should match
+    /// the namespace the runtime registers host imports in.
+    #[darling(default)]
+    pub(crate) host_imports_namespace: Option<String>,
+
+    /// Module names mapped to paths to their crates, compilation fails if these modules are
+    /// older than their sources, they are rebuilt by `build_modules` from a build script.
+    #[darling(default)]
+    pub(crate) build: HashMap<String, String>,
+
+    /// Overrides of module configs applied to the loaded service config.
+    #[darling(default)]
+    pub(crate) config_overrides: ConfigOverrides,
+
+    /// Derives added to generated records, f.e. `derives(PartialOrd, Ord)`.
+    #[darling(default)]
+    pub(crate) derives: PathList,
+
+    /// Several services tested together, each of them gets its own handle and namespace.
+    #[darling(default)]
+    pub(crate) services: HashMap<String, ServiceDescription>,
+}
+
+/// Describes one of services tested together.
+#[derive(Debug, Default, Clone, FromMeta)]
+pub(crate) struct ServiceDescription {
+    /// Path to a config file of a service.
     pub(crate) config_path: String,
 
     /// Path to compiled modules of a service.
     #[darling(default)]
     pub(crate) modules_dir: Option<String>,
+
+    /// Mocks of host imports of service modules.
+    #[darling(default)]
+    pub(crate) host_imports: HostImportMocks,
+
+    /// Namespace mocked host imports of service modules are looked up in.
+    #[darling(default)]
+    pub(crate) host_imports_namespace: Option<String>,
+
+    /// Module names mapped to paths to their crates that are checked for staleness.
+    #[darling(default)]
+    pub(crate) build: HashMap<String, String>,
+
+    /// Overrides of configs of service modules.
+    #[darling(default)]
+    pub(crate) config_overrides: ConfigOverrides,
+
+    /// Derives added to records of service modules.
+    #[darling(default)]
+    pub(crate) derives: PathList,
+}
+
+/// Module names mapped to host import names and mocks of these imports, f.e.
+/// `host_imports(greeting(curl = "|args: Vec<String>| mock_curl(args)"))`.
+pub(crate) type HostImportMocks = HashMap<String, HashMap<String, String>>;
+
+/// Module names mapped to overrides of their configs, f.e.
+/// `config_overrides(greeting(envs(LOG_LEVEL = "debug"), mem_pages_count = 100))`.
+pub(crate) type ConfigOverrides = HashMap<String, ModuleConfigOverrides>;
+
+/// Settings of a module config that could be overridden in a test, envs, mapped dirs and
+/// mounted binaries are added to ones from the config replacing values with the same names.
+#[derive(Debug, Default, Clone, FromMeta)]
+pub(crate) struct ModuleConfigOverrides {
+    #[darling(default)]
+    pub(crate) envs: HashMap<String, String>,
+
+    /// Aliases mapped to host directories.
+    #[darling(default)]
+    pub(crate) mapped_dirs: HashMap<String, String>,
+
+    /// Import names mapped to paths to binaries on the host.
+    #[darling(default)]
+    pub(crate) mounted_binaries: HashMap<String, String>,
+
+    /// Maximum count of memory pages a module could use.
+    #[darling(default)]
+    pub(crate) mem_pages_count: Option<u32>,
+
+    #[darling(default)]
+    pub(crate) logger_enabled: Option<bool>,
+
+    #[darling(default)]
+    pub(crate) logging_mask: Option<i32>,
 }
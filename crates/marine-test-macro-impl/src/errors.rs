@@ -45,6 +45,53 @@ pub enum TestGeneratorError {
     )]
     ModulesDirUnspecified,
 
+    #[error("neither config_path nor services attribute specified, please specify one of them")]
+    ConfigPathUnspecified,
+
+    #[error(
+        "config_path and modules_dir attributes can't be used with services, please specify them for each service"
+    )]
+    ConfigPathWithServices,
+
+    #[error("module {0} specified in host_imports isn't found in the service config")]
+    UnknownMockedModule(String),
+
+    #[error("module {module_name} doesn't import {import_name} from the {namespace} namespace")]
+    UnknownHostImport {
+        module_name: String,
+        import_name: String,
+        namespace: String,
+    },
+
+    #[error("module {module_path:?} can't be loaded to read its imports: {reason}")]
+    ModuleLoadFailed {
+        module_path: PathBuf,
+        reason: String,
+    },
+
+    #[error("module {0} specified in build isn't found in the service config")]
+    UnknownBuiltModule(String),
+
+    #[error("module {module_name} can't be built: {reason}")]
+    ModuleBuildFailed { module_name: String, reason: String },
+
+    #[error(
+        "module {0} is absent or older than its crate, please rebuild it by marine_rs_sdk_test::build_modules in a build script"
+    )]
+    StaleModule(String),
+
+    #[error("module {module_name} can't be checked for staleness: {reason}")]
+    ModuleCheckFailed { module_name: String, reason: String },
+
+    #[error("module {0} specified in config_overrides isn't found in the service config")]
+    UnknownOverriddenModule(String),
+
+    #[error("marine_test can be applied only to functions and modules")]
+    UnsupportedItem,
+
+    #[error("marine_test can't be applied to a module declared without body")]
+    ModWithoutBody,
+
     #[error("a Wasm file compiled with newer version of sdk that supports multi-value")]
     ManyFnOutputsUnsupported,
 
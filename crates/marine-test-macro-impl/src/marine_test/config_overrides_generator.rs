@@ -0,0 +1,122 @@
+/*
+ * Copyright 2021 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::attributes::ConfigOverrides;
+use crate::attributes::ModuleConfigOverrides;
+use crate::TResult;
+use crate::TestGeneratorError;
+
+use fluence_app_service::TomlAppServiceConfig;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use std::collections::HashMap;
+
+/// Generates statements that apply overrides to `__m_generated_marine_config` before
+/// it's converted to `AppServiceConfig`. F.e. for
+/// `config_overrides(greeting(envs(LOG_LEVEL = "debug"), mem_pages_count = 100))`
+/// the following code would be generated:
+///```ignore
+/// marine_rs_sdk_test::internal::override_module_config(
+///     &mut __m_generated_marine_config,
+///     "greeting",
+///     marine_rs_sdk_test::internal::ModuleConfigOverrides {
+///         envs: vec![("LOG_LEVEL".to_string(), "debug".to_string())],
+///         mapped_dirs: vec![],
+///         mounted_binaries: vec![],
+///         mem_pages_count: Some(100u32),
+///         logger_enabled: None,
+///         logging_mask: None,
+///     },
+/// );
+///```
+pub(super) fn generate_config_overrides(
+    marine_config: &TomlAppServiceConfig,
+    overrides: ConfigOverrides,
+) -> TResult<Vec<TokenStream>> {
+    // sort overrides to make generated code independent of the HashMap order
+    let mut overrides = overrides.into_iter().collect::<Vec<_>>();
+    overrides.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+
+    overrides
+        .into_iter()
+        .map(|(module_name, module_overrides)| {
+            let is_module_known = marine_config
+                .toml_faas_config
+                .module
+                .iter()
+                .any(|module| module.name == module_name);
+            if !is_module_known {
+                return Err(TestGeneratorError::UnknownOverriddenModule(module_name));
+            }
+
+            let module_overrides = generate_module_overrides(module_overrides);
+            let override_statement = quote! {
+                marine_rs_sdk_test::internal::override_module_config(
+                    &mut __m_generated_marine_config,
+                    #module_name,
+                    #module_overrides,
+                );
+            };
+
+            Ok(override_statement)
+        })
+        .collect::<TResult<Vec<_>>>()
+}
+
+fn generate_module_overrides(overrides: ModuleConfigOverrides) -> TokenStream {
+    let ModuleConfigOverrides {
+        envs,
+        mapped_dirs,
+        mounted_binaries,
+        mem_pages_count,
+        logger_enabled,
+        logging_mask,
+    } = overrides;
+
+    let envs = generate_pairs(envs);
+    let mapped_dirs = generate_pairs(mapped_dirs);
+    let mounted_binaries = generate_pairs(mounted_binaries);
+    let mem_pages_count = generate_option(mem_pages_count);
+    let logger_enabled = generate_option(logger_enabled);
+    let logging_mask = generate_option(logging_mask);
+
+    quote! {
+        marine_rs_sdk_test::internal::ModuleConfigOverrides {
+            envs: #envs,
+            mapped_dirs: #mapped_dirs,
+            mounted_binaries: #mounted_binaries,
+            mem_pages_count: #mem_pages_count,
+            logger_enabled: #logger_enabled,
+            logging_mask: #logging_mask,
+        }
+    }
+}
+
+fn generate_pairs(map: HashMap<String, String>) -> TokenStream {
+    let mut pairs = map.into_iter().collect::<Vec<_>>();
+    pairs.sort();
+
+    let (keys, values): (Vec<_>, Vec<_>) = pairs.into_iter().unzip();
+    quote! { vec![#((#keys.to_string(), #values.to_string())),*] }
+}
+
+fn generate_option<T: quote::ToTokens>(value: Option<T>) -> TokenStream {
+    match value {
+        Some(value) => quote! { Some(#value) },
+        None => quote! { None },
+    }
+}
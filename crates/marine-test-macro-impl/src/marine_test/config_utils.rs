@@ -15,22 +15,36 @@
  */
 
 use crate::TResult;
+use crate::TestGeneratorError;
 
 use fluence_app_service::TomlAppServiceConfig;
+use marine_it_parser::extract_it_from_module;
 use marine_it_parser::module_raw_interface;
+use marine_it_parser::ITParserError;
 use marine_it_parser::interface::MModuleInterface;
+use marine_it_parser::interface::MFunctionSignature;
 
+use std::path::Path;
 use std::path::PathBuf;
+use std::rc::Rc;
+
+/// Namespace Marine registers host imports of modules in.
+pub(super) const DEFAULT_HOST_IMPORTS_NAMESPACE: &str = "host";
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(super) struct Module<'m> {
     pub name: &'m str,
+    pub path: PathBuf,
     pub interface: MModuleInterface,
 }
 
 impl<'m> Module<'m> {
-    fn new(name: &'m str, interface: MModuleInterface) -> Self {
-        Self { name, interface }
+    fn new(name: &'m str, path: PathBuf, interface: MModuleInterface) -> Self {
+        Self {
+            name,
+            path,
+            interface,
+        }
     }
 }
 
@@ -44,13 +58,13 @@ pub(super) fn collect_modules(
     module_paths
         .into_iter()
         .map(|(name, path)| {
-            module_raw_interface(path).map(|interface| Module::new(name, interface))
+            module_raw_interface(&path).map(|interface| Module::new(name, path, interface))
         })
         .collect::<Result<Vec<_>, _>>()
         .map_err(Into::into)
 }
 
-fn collect_module_paths(
+pub(super) fn collect_module_paths(
     config: &TomlAppServiceConfig,
     modules_dir: PathBuf,
 ) -> Vec<(&str, PathBuf)> {
@@ -86,3 +100,53 @@ pub(super) fn resolve_modules_dir(
             .map(PathBuf::from),
     }
 }
+
+/// Returns signatures of functions the module imports from the provided namespace. The IT
+/// section is extracted by marine-it-parser and imports are matched with their adapter types
+/// in the same way as Marine does it while instantiating a module.
+pub(super) fn collect_host_imports(
+    module_path: &Path,
+    namespace: &str,
+) -> TResult<Vec<MFunctionSignature>> {
+    use wasmer_it::ast::Type as ITAstType;
+
+    let module_load_error = |reason: String| TestGeneratorError::ModuleLoadFailed {
+        module_path: module_path.to_path_buf(),
+        reason,
+    };
+    let wasm_bytes = std::fs::read(module_path).map_err(|e| module_load_error(e.to_string()))?;
+    let module =
+        wasmer_runtime::compile(&wasm_bytes).map_err(|e| module_load_error(format!("{:?}", e)))?;
+    let it = extract_it_from_module(&module)?;
+
+    let mut imports = Vec::new();
+    for implementation in &it.implementations {
+        let namespace_imports = it.imports.iter().filter(|import| {
+            import.function_type == implementation.core_function_type
+                && import.namespace == namespace
+        });
+
+        for import in namespace_imports {
+            let adapter_function_type = implementation.adapter_function_type;
+            match it.types.get(adapter_function_type as usize) {
+                Some(ITAstType::Function {
+                    arguments,
+                    output_types,
+                }) => imports.push(MFunctionSignature {
+                    name: Rc::new(import.name.to_string()),
+                    arguments: arguments.clone(),
+                    outputs: output_types.clone(),
+                }),
+                _ => {
+                    let message = format!(
+                        "type with idx = {} isn't a function type",
+                        adapter_function_type
+                    );
+                    return Err(ITParserError::IncorrectITFormat(message).into());
+                }
+            }
+        }
+    }
+
+    Ok(imports)
+}
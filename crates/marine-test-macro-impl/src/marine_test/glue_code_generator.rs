@@ -15,15 +15,21 @@
  */
 
 use crate::attributes::MTestAttributes;
+use crate::attributes::ServiceDescription;
 use crate::TResult;
 use crate::TestGeneratorError;
 use crate::marine_test;
+use crate::marine_test::config_overrides_generator;
 use crate::marine_test::config_utils;
+use crate::marine_test::host_imports_generator;
+use crate::marine_test::modules_builder;
 
 use fluence_app_service::TomlAppServiceConfig;
 use proc_macro2::TokenStream;
 use quote::quote;
+use quote::ToTokens;
 
+use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
 
@@ -56,112 +62,455 @@ use std::path::PathBuf;
 ///         pub fn greeting(&mut self, name: String) -> String {
 ///             use std::ops::DerefMut;
 ///             let arguments = marine_rs_sdk_test::internal::serde_json::json!([name]);
-///             let result = self
-///                 .marine
-///                 .as_ref
-///                 .borrow_mut()
-///                 .call_with_module_name("greeting", "greeting", arguments, <_>::default())
-///                 .expect("call to Marine failed");
+///             let result = marine_rs_sdk_test::internal::call_module(
+///                 &self.marine,
+///                 "greeting",
+///                 "greeting",
+///                 arguments,
+///                 <_>::default(),
+///             )
+///             .expect("call to Marine failed");
 ///             let result: String = marine_rs_sdk_test::internal::serde_json::from_value(result)
 ///                 .expect("the default deserializer shouldn't fail");
 ///             result
 ///         }
 ///     }
 ///}
-/// // (1)
-/// let tmp_dir = std::env::temp_dir();
-/// let service_id = marine_rs_sdk_test::internal::Uuid::new_v4().to_string();
+/// fn __m_generated_app_service() -> std::rc::Rc<std::cell::RefCell<marine_rs_sdk_test::internal::AppService>> {
+///     let tmp_dir = std::env::temp_dir();
+///     let service_id = marine_rs_sdk_test::internal::Uuid::new_v4().to_string();
 ///
-/// let tmp_dir = tmp_dir.join(&service_id);
-/// let tmp_dir = tmp_dir.to_string_lossy().to_string();
-/// std::fs::create_dir(&tmp_dir).expect("can't create a directory for service in tmp");
+///     let tmp_dir = tmp_dir.join(&service_id);
+///     let tmp_dir = tmp_dir.to_string_lossy().to_string();
+///     std::fs::create_dir(&tmp_dir).expect("can't create a directory for service in tmp");
 ///
-/// let mut __m_generated_marine_config = marine_rs_sdk_test::internal::TomlAppServiceConfig::load("/path/to/greeting/Config.toml".to_string())
-///     .unwrap_or_else(|e| {
-///         panic!(
-///              "app service located at `{}` config can't be loaded: {}",
-///            "/path/to/greeting/Config.toml", e
-///         )
-///      });
+///     let mut __m_generated_marine_config = marine_rs_sdk_test::internal::TomlAppServiceConfig::load("/path/to/greeting/Config.toml".to_string())
+///         .unwrap_or_else(|e| {
+///             panic!(
+///                  "app service located at `{}` config can't be loaded: {}",
+///                "/path/to/greeting/Config.toml", e
+///             )
+///          });
 ///
-/// __m_generated_marine_config.service_base_dir = Some("/path/to/tmp".to_string());
+///     __m_generated_marine_config.service_base_dir = Some("/path/to/tmp".to_string());
 ///
-/// let marine = marine_rs_sdk_test::internal::AppService::new_with_empty_facade(
-///         __m_generated_marine_config,
-///         "3640e972-92e3-47cb-b95f-4e3c5bcf0f14",
-///         std::collections::HashMap::new(),
-///     ).unwrap_or_else(|e| panic!("app service can't be created: {}", e));
+///     let marine = marine_rs_sdk_test::internal::AppService::new_with_empty_facade(
+///             __m_generated_marine_config,
+///             "3640e972-92e3-47cb-b95f-4e3c5bcf0f14",
+///             std::collections::HashMap::new(),
+///         ).unwrap_or_else(|e| panic!("app service can't be created: {}", e));
 ///
-/// let marine = std::rc::Rc::new(std::cell::RefCell::new(marine));
+///     let marine = std::rc::Rc::new(std::cell::RefCell::new(marine));
 ///
-/// // (2)
+///     marine
+/// }
+/// // (1)
+/// let marine = __m_generated_app_service();
 ///
 /// let mut greeting = __m_generated_greeting::MGeneratedStructgreeting::new(marine);
 ///
-/// // (3)
+/// // (2)
 ///
 /// let result = greeting.greeting("John".to_string());
 /// assert_eq(result.as_str(), "Hi, John!");
 ///
-/// // (4)
+/// // (3)
 ///```
 ///
 /// Example code above corresponds to the macro definition in the following way:
-///      [(0), (1)] - module_definitions*
-///      [(1), (2)] - app_service_ctor
-///      [(2), (3)] - module_ctors*
-///      [(3), (4)] - original_block
+///      [(0), (1)] - definitions*
+///      [(1), (2)] - instantiation
+///      [(2), (3)] - original_block
+///
+/// If several services are specified by the `services` attribute, definitions of each service
+/// are placed into a module named after the service, so records with the same names don't
+/// collide. Each service is instantiated to a variable with the same name:
+///```ignore
+/// #[marine_test(services(
+///     auth(config_path = "auth/Config.toml"),
+///     storage(config_path = "storage/Config.toml", modules_dir = "storage/artifacts")
+/// ))]
+/// fn test() {
+///     let token: auth::auth_structs::Token = auth.auth.get_token("John".to_string());
+///     let result = storage.storage.get("key".to_string(), token);
+/// }
+///```
 pub(super) fn generate_test_glue_code(
     func_item: syn::ItemFn,
     attrs: MTestAttributes,
     file_path: PathBuf,
 ) -> TResult<TokenStream> {
-    let config_path = file_path.join(&attrs.config_path);
+    let TestEnvironment {
+        definitions,
+        instantiation,
+    } = generate_test_environment(attrs, &file_path)?;
+
+    let original_block = func_item.block;
+    let signature = func_item.sig;
+
+    let glue_code = quote! {
+        #[test]
+        #signature {
+            // definitions for wasm modules specified in config
+            #(#definitions)*
+
+            // instantiation of the tested services and their modules
+            #instantiation
+
+            // original test function as is
+            #original_block
+        }
+    };
+
+    Ok(glue_code)
+}
+
+/// Generates glue code for a module with tests. Definitions of modules are generated once
+/// at the module level, so they could be used by helper functions, and each function marked
+/// with `#[test]` instantiates fresh services:
+///```ignore
+/// #[marine_test(config_path = "/path/to/service/config/Config.toml")]
+/// mod tests {
+///     fn greet(greeting: &mut greeting_structs::MGeneratedStructgreeting) -> String {
+///         greeting.greeting("John".to_string())
+///     }
+///
+///     #[test]
+///     fn test() {
+///         assert_eq!(greet(&mut greeting), "Hi, John!");
+///     }
+/// }
+///```
+pub(super) fn generate_test_mod_glue_code(
+    mod_item: syn::ItemMod,
+    attrs: MTestAttributes,
+    file_path: PathBuf,
+) -> TResult<TokenStream> {
+    let items = match mod_item.content {
+        Some((_, items)) => items,
+        None => return Err(TestGeneratorError::ModWithoutBody),
+    };
+
+    let TestEnvironment {
+        definitions,
+        instantiation,
+    } = generate_test_environment(attrs, &file_path)?;
+
+    let items = items.into_iter().map(|item| match item {
+        syn::Item::Fn(func_item) if is_test_function(&func_item) => {
+            let syn::ItemFn {
+                attrs,
+                vis,
+                sig,
+                block,
+            } = func_item;
+
+            quote! {
+                #(#attrs)*
+                #vis #sig {
+                    #instantiation
+
+                    #block
+                }
+            }
+        }
+        item => item.into_token_stream(),
+    });
+
+    let mod_attrs = mod_item.attrs;
+    let mod_vis = mod_item.vis;
+    let mod_ident = mod_item.ident;
+
+    let glue_code = quote! {
+        #(#mod_attrs)*
+        #mod_vis mod #mod_ident {
+            // definitions for wasm modules specified in config, shared by all tests
+            #(#definitions)*
+
+            #(#items)*
+        }
+    };
+
+    Ok(glue_code)
+}
+
+/// Only the plain `#[test]` attribute is recognized, functions marked by other attributes
+/// like `#[tokio::test]` are left as is, because they could require a different environment.
+fn is_test_function(func_item: &syn::ItemFn) -> bool {
+    func_item
+        .attrs
+        .iter()
+        .any(|attr| attr.path.is_ident("test"))
+}
+
+/// Code shared by all tests generated by one marine_test invocation.
+struct TestEnvironment {
+    /// Definitions of modules, records and services.
+    definitions: Vec<TokenStream>,
+
+    /// Instantiation of services and their modules at the beginning of each test.
+    instantiation: TokenStream,
+}
+
+fn generate_test_environment(attrs: MTestAttributes, file_path: &Path) -> TResult<TestEnvironment> {
+    let MTestAttributes {
+        config_path,
+        modules_dir,
+        host_imports,
+        host_imports_namespace,
+        build,
+        config_overrides,
+        derives,
+        services,
+    } = attrs;
+
+    let has_service_settings = modules_dir.is_some()
+        || !host_imports.is_empty()
+        || host_imports_namespace.is_some()
+        || !build.is_empty()
+        || !config_overrides.is_empty()
+        || !derives.is_empty();
 
-    let marine_config = TomlAppServiceConfig::load(&config_path)?;
-    let modules_dir = match config_utils::resolve_modules_dir(&marine_config, attrs.modules_dir) {
+    match (config_path, services.is_empty()) {
+        (Some(config_path), true) => {
+            let description = ServiceDescription {
+                config_path,
+                modules_dir,
+                host_imports,
+                host_imports_namespace,
+                build,
+                config_overrides,
+                derives,
+            };
+            generate_single_service_environment(description, file_path)
+        }
+        (None, false) if !has_service_settings => {
+            generate_multi_service_environment(services, file_path)
+        }
+        (None, true) => Err(TestGeneratorError::ConfigPathUnspecified),
+        _ => Err(TestGeneratorError::ConfigPathWithServices),
+    }
+}
+
+/// Definitions of modules of one service and code that instantiates this service.
+struct ServiceBindings {
+    module_definitions: Vec<TokenStream>,
+    app_service_ctor: TokenStream,
+    module_names: Vec<String>,
+}
+
+fn generate_service_bindings(
+    description: ServiceDescription,
+    file_path: &Path,
+) -> TResult<ServiceBindings> {
+    let ServiceDescription {
+        config_path,
+        modules_dir,
+        host_imports,
+        host_imports_namespace,
+        build,
+        config_overrides,
+        derives,
+    } = description;
+    let full_config_path = file_path.join(&config_path);
+
+    let marine_config = TomlAppServiceConfig::load(&full_config_path)?;
+    let modules_dir = match config_utils::resolve_modules_dir(&marine_config, modules_dir) {
         Some(modules_dir) => modules_dir,
         None => return Err(TestGeneratorError::ModulesDirUnspecified),
     };
 
-    let app_service_ctor = generate_app_service_ctor(&attrs.config_path, &modules_dir)?;
-    let modules_dir = file_path.join(modules_dir);
+    let full_modules_dir = file_path.join(&modules_dir);
+    modules_builder::check_modules(&marine_config, &full_modules_dir, build, file_path)?;
+
     let module_interfaces =
-        marine_test::config_utils::collect_modules(&marine_config, modules_dir)?;
+        marine_test::config_utils::collect_modules(&marine_config, full_modules_dir)?;
+
+    let host_imports_namespace = host_imports_namespace
+        .as_deref()
+        .unwrap_or(config_utils::DEFAULT_HOST_IMPORTS_NAMESPACE);
+    let host_import_mocks = host_imports_generator::generate_host_import_mocks(
+        &module_interfaces,
+        host_imports,
+        host_imports_namespace,
+    )?;
+    let config_overrides =
+        config_overrides_generator::generate_config_overrides(&marine_config, config_overrides)?;
+    let app_service_ctor = generate_app_service_ctor(
+        &config_path,
+        &modules_dir,
+        config_overrides,
+        host_import_mocks,
+    )?;
+
+    let module_definitions = marine_test::module_generator::generate_module_definitions(
+        module_interfaces.iter(),
+        &derives,
+    )?;
+
+    let module_names = module_interfaces
+        .iter()
+        .map(|module| module.name.to_string())
+        .collect();
+
+    let bindings = ServiceBindings {
+        module_definitions,
+        app_service_ctor,
+        module_names,
+    };
+
+    Ok(bindings)
+}
 
-    let module_definitions =
-        marine_test::module_generator::generate_module_definitions(module_interfaces.iter())?;
+fn generate_single_service_environment(
+    description: ServiceDescription,
+    file_path: &Path,
+) -> TResult<TestEnvironment> {
+    let ServiceBindings {
+        mut module_definitions,
+        app_service_ctor,
+        module_names,
+    } = generate_service_bindings(description, file_path)?;
 
-    let module_iter = module_interfaces.iter().map(|module| module.name);
+    let module_iter = module_names.iter().map(String::as_str);
     let module_ctors = generate_module_ctors(module_iter)?;
 
-    let original_block = func_item.block;
-    let signature = func_item.sig;
+    module_definitions.push(quote! {
+        fn __m_generated_app_service() -> std::rc::Rc<std::cell::RefCell<marine_rs_sdk_test::internal::AppService>> {
+            #app_service_ctor
 
-    let glue_code = quote! {
-        #[test]
-        #signature {
-            // definitions for wasm modules specified in config
+            marine
+        }
+    });
+
+    let instantiation = quote! {
+        // AppService instantiation to implicit `marine` variable
+        let marine = __m_generated_app_service();
+
+        // constructors of all modules of the tested service
+        #(#module_ctors)*
+    };
+
+    let environment = TestEnvironment {
+        definitions: module_definitions,
+        instantiation,
+    };
+
+    Ok(environment)
+}
+
+fn generate_multi_service_environment(
+    services: HashMap<String, ServiceDescription>,
+    file_path: &Path,
+) -> TResult<TestEnvironment> {
+    // sort services to make generated code independent of the HashMap order
+    let mut services = services.into_iter().collect::<Vec<_>>();
+    services.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+
+    let (service_definitions, service_ctors): (Vec<_>, Vec<_>) = services
+        .into_iter()
+        .map(|(name, description)| -> TResult<_> {
+            let bindings = generate_service_bindings(description, file_path)?;
+            let service_definition = generate_service_definition(&name, bindings)?;
+
+            let service_ident = marine_test::utils::new_ident(&name)?;
+            let service_ctor =
+                quote! { let mut #service_ident = #service_ident::ServiceInterface::new(); };
+
+            Ok((service_definition, service_ctor))
+        })
+        .collect::<TResult<Vec<_>>>()?
+        .into_iter()
+        .unzip();
+
+    let instantiation = quote! {
+        // constructors of all tested services
+        #(#service_ctors)*
+    };
+
+    let environment = TestEnvironment {
+        definitions: service_definitions,
+        instantiation,
+    };
+
+    Ok(environment)
+}
+
+/// Generates a module named after a service, that contains definitions of its modules and
+/// the ServiceInterface struct with a field per module.
+fn generate_service_definition(
+    service_name: &str,
+    bindings: ServiceBindings,
+) -> TResult<TokenStream> {
+    let ServiceBindings {
+        module_definitions,
+        app_service_ctor,
+        module_names,
+    } = bindings;
+
+    let service_ident = marine_test::utils::new_ident(service_name)?;
+    let (field_idents, struct_paths): (Vec<_>, Vec<_>) = module_names
+        .iter()
+        .map(|name| -> TResult<_> {
+            let field_ident = marine_test::utils::new_ident(name)?;
+            let module_ident = marine_test::utils::generate_structs_module_ident(name)?;
+            let struct_ident = marine_test::utils::generate_struct_name(name)?;
+
+            Ok((field_ident, quote! { #module_ident::#struct_ident }))
+        })
+        .collect::<TResult<Vec<_>>>()?
+        .into_iter()
+        .unzip();
+
+    let service_definition = quote! {
+        pub mod #service_ident {
             #(#module_definitions)*
 
-            // AppService constructor and instantiation to implicit `marine` variable
-            #app_service_ctor
+            pub struct ServiceInterface {
+                #(pub #field_idents: #struct_paths,)*
+                __m_generated_marine: std::rc::Rc<std::cell::RefCell<marine_rs_sdk_test::internal::AppService>>,
+            }
 
-            // constructors of all modules of the tested service
-            #(#module_ctors)*
+            impl ServiceInterface {
+                pub fn new() -> Self {
+                    // makes items of the enclosing scope available to host import mocks
+                    #[allow(unused_imports)]
+                    use super::*;
 
-            // original test function as is
-            #original_block
+                    #app_service_ctor
+
+                    Self {
+                        #(#field_idents: #struct_paths::new(marine.clone()),)*
+                        __m_generated_marine: marine,
+                    }
+                }
+
+                /// Returns the AppService shared by all modules of this service,
+                /// f.e. to replay calls by `CallLog::replay`.
+                pub fn app_service(&self) -> std::rc::Rc<std::cell::RefCell<marine_rs_sdk_test::internal::AppService>> {
+                    self.__m_generated_marine.clone()
+                }
+            }
         }
     };
 
-    Ok(glue_code)
+    Ok(service_definition)
 }
 
-fn generate_app_service_ctor(config_path: &str, modules_dir: &Path) -> TResult<TokenStream> {
+fn generate_app_service_ctor(
+    config_path: &str,
+    modules_dir: &Path,
+    config_overrides: Vec<TokenStream>,
+    host_import_mocks: Vec<TokenStream>,
+) -> TResult<TokenStream> {
     let modules_dir = modules_dir
         .to_str()
         .ok_or_else(|| TestGeneratorError::InvalidUTF8Path(modules_dir.to_path_buf()))?;
+    let maybe_mut = if host_import_mocks.is_empty() {
+        TokenStream::new()
+    } else {
+        quote! { mut }
+    };
 
     let service_ctor = quote! {
         let tmp_dir = std::env::temp_dir();
@@ -208,6 +557,11 @@ fn generate_app_service_ctor(config_path: &str, modules_dir: &Path) -> TResult<T
             .unwrap_or_else(|e| panic!("app service config located at `{:?}` can't be loaded: {}", config_path, e));
         __m_generated_marine_config.service_base_dir = Some(tmp_dir);
         __m_generated_marine_config.toml_faas_config.modules_dir = Some(modules_dir.to_string());
+        #(#config_overrides)*
+
+        let #maybe_mut __m_generated_marine_config: marine_rs_sdk_test::internal::AppServiceConfig = std::convert::TryInto::try_into(__m_generated_marine_config)
+            .unwrap_or_else(|e| panic!("app service config located at `{:?}` is invalid: {}", config_path, e));
+        #(#host_import_mocks)*
 
         let marine = marine_rs_sdk_test::internal::AppService::new_with_empty_facade(__m_generated_marine_config, service_id, std::collections::HashMap::new())
             .unwrap_or_else(|e| panic!("app service can't be created: {}", e));
@@ -225,9 +579,9 @@ fn generate_module_ctors<'n>(
         .map(|name| -> TResult<_> {
             // TODO: optimize these two call because they are called twice for each module name
             // and internally allocate memory in format call.
-            let module_name = marine_test::utils::generate_structs_module_ident(&name)?;
-            let struct_name = marine_test::utils::generate_struct_name(&name)?;
-            let name_for_user = marine_test::utils::new_ident(&name)?;
+            let module_name = marine_test::utils::generate_structs_module_ident(name)?;
+            let struct_name = marine_test::utils::generate_struct_name(name)?;
+            let name_for_user = marine_test::utils::new_ident(name)?;
 
             let module_ctor =
                 quote! { let mut #name_for_user = #module_name::#struct_name::new(marine.clone()); };
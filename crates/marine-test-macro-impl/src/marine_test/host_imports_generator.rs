@@ -0,0 +1,179 @@
+/*
+ * Copyright 2021 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::attributes::HostImportMocks;
+use crate::marine_test::config_utils;
+use crate::marine_test::config_utils::Module;
+use crate::marine_test::utils;
+use crate::TResult;
+use crate::TestGeneratorError;
+
+use marine_it_parser::interface::MFunctionSignature;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Generates statements that set mocks of host imports from the provided namespace
+/// to `__m_generated_marine_config`.
+/// F.e. for `host_imports(greeting(curl = "mock_curl"))`, where `curl` takes `Vec<String>`
+/// and returns `MountedBinaryResult`, the following code would be generated:
+///```ignore
+/// marine_rs_sdk_test::internal::set_host_import(
+///     &mut __m_generated_marine_config,
+///     "greeting",
+///     "curl",
+///     {
+///         let mock = mock_curl;
+///         marine_rs_sdk_test::internal::create_host_import(
+///             move |arguments: Vec<marine_rs_sdk_test::internal::IValue>| {
+///                 use greeting_structs::*;
+///
+///                 let (__m_generated_arg_0,): (Vec<String>,) =
+///                     marine_rs_sdk_test::internal::from_interface_values(&arguments)
+///                         .unwrap_or_else(|e| panic!("..."));
+///                 let result: MountedBinaryResult = mock(__m_generated_arg_0);
+///                 let result = marine_rs_sdk_test::internal::to_interface_value(&result)
+///                     .unwrap_or_else(|e| panic!("..."));
+///                 Some(result)
+///             },
+///             vec![marine_rs_sdk_test::internal::IType::Array(Box::new(marine_rs_sdk_test::internal::IType::String))],
+///             Some(marine_rs_sdk_test::internal::IType::Record(0)),
+///         )
+///     },
+/// );
+///```
+pub(super) fn generate_host_import_mocks(
+    modules: &[Module<'_>],
+    mocks: HostImportMocks,
+    namespace: &str,
+) -> TResult<Vec<TokenStream>> {
+    // sort mocks to make generated code independent of the HashMap order
+    let mut mocks = mocks.into_iter().collect::<Vec<_>>();
+    mocks.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+
+    let mut mock_setters = Vec::new();
+    for (module_name, module_mocks) in mocks {
+        let module = match modules.iter().find(|module| module.name == module_name) {
+            Some(module) => module,
+            None => return Err(TestGeneratorError::UnknownMockedModule(module_name)),
+        };
+        let host_imports = config_utils::collect_host_imports(&module.path, namespace)?;
+
+        let mut module_mocks = module_mocks.into_iter().collect::<Vec<_>>();
+        module_mocks.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+
+        for (import_name, mock) in module_mocks {
+            let signature = host_imports
+                .iter()
+                .find(|signature| signature.name.as_str() == import_name)
+                .ok_or_else(|| TestGeneratorError::UnknownHostImport {
+                    module_name: module_name.clone(),
+                    import_name: import_name.clone(),
+                    namespace: namespace.to_string(),
+                })?;
+
+            let mock = syn::parse_str::<syn::Expr>(&mock)?;
+            let host_import = generate_host_import(module, signature, mock)?;
+
+            mock_setters.push(quote! {
+                marine_rs_sdk_test::internal::set_host_import(
+                    &mut __m_generated_marine_config,
+                    #module_name,
+                    #import_name,
+                    #host_import,
+                );
+            });
+        }
+    }
+
+    Ok(mock_setters)
+}
+
+fn generate_host_import(
+    module: &Module<'_>,
+    signature: &MFunctionSignature,
+    mock: syn::Expr,
+) -> TResult<TokenStream> {
+    let records = &module.interface.record_types;
+    let import_name = signature.name.as_str();
+    let structs_module_ident = utils::generate_structs_module_ident(module.name)?;
+
+    let arg_idents = (0..signature.arguments.len())
+        .map(|idx| utils::new_ident(&format!("__m_generated_arg_{}", idx)))
+        .collect::<TResult<Vec<_>>>()?;
+    let arg_types = signature
+        .arguments
+        .iter()
+        .map(|argument| utils::itype_to_tokens(&argument.ty, records))
+        .collect::<TResult<Vec<_>>>()?;
+    let argument_itypes = signature
+        .arguments
+        .iter()
+        .map(|argument| utils::itype_to_value_tokens(&argument.ty));
+
+    let convert_arguments = if arg_idents.is_empty() {
+        quote! { let _ = arguments; }
+    } else {
+        quote! {
+            #[allow(deprecated)]
+            let (#(#arg_idents,)*): (#(#arg_types,)*) = marine_rs_sdk_test::internal::from_interface_values(&arguments)
+                .unwrap_or_else(|e| panic!("arguments of the {} host import mock can't be deserialized: {}", #import_name, e));
+        }
+    };
+
+    let (output_itype, call_mock) = match signature.outputs.as_slice() {
+        [] => {
+            let call_mock = quote! {
+                mock(#(#arg_idents),*);
+                None
+            };
+
+            (quote! { None }, call_mock)
+        }
+        [output_type] => {
+            let output_rust_type = utils::itype_to_tokens(output_type, records)?;
+            let output_itype = utils::itype_to_value_tokens(output_type);
+            let call_mock = quote! {
+                #[allow(deprecated)]
+                let result: #output_rust_type = mock(#(#arg_idents),*);
+                let result = marine_rs_sdk_test::internal::to_interface_value(&result)
+                    .unwrap_or_else(|e| panic!("result of the {} host import mock can't be serialized: {}", #import_name, e));
+                Some(result)
+            };
+
+            (quote! { Some(#output_itype) }, call_mock)
+        }
+        _ => return Err(TestGeneratorError::ManyFnOutputsUnsupported),
+    };
+
+    let host_import = quote! {
+        {
+            let mock = #mock;
+            marine_rs_sdk_test::internal::create_host_import(
+                move |arguments: Vec<marine_rs_sdk_test::internal::IValue>| {
+                    #[allow(unused_imports)]
+                    use #structs_module_ident::*;
+
+                    #convert_arguments
+                    #call_mock
+                },
+                vec![#(#argument_itypes),*],
+                #output_itype,
+            )
+        }
+    };
+
+    Ok(host_import)
+}
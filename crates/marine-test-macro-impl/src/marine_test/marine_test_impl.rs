@@ -16,7 +16,9 @@
 
 use crate::attributes::MTestAttributes;
 use crate::TResult;
+use crate::TestGeneratorError;
 use crate::marine_test::glue_code_generator::generate_test_glue_code;
+use crate::marine_test::glue_code_generator::generate_test_mod_glue_code;
 
 use proc_macro2::TokenStream;
 use darling::FromMeta;
@@ -34,7 +36,9 @@ pub fn marine_test_impl(
     let attrs: Vec<syn::NestedMeta> = attrs.into_iter().collect();
     let attrs = MTestAttributes::from_list(&attrs)?;
 
-    let func_item = syn::parse2::<syn::ItemFn>(input)?;
-
-    generate_test_glue_code(func_item, attrs, file_path)
+    match syn::parse2::<syn::Item>(input)? {
+        syn::Item::Fn(func_item) => generate_test_glue_code(func_item, attrs, file_path),
+        syn::Item::Mod(mod_item) => generate_test_mod_glue_code(mod_item, attrs, file_path),
+        _ => Err(TestGeneratorError::UnsupportedItem),
+    }
 }
@@ -14,10 +14,14 @@
  * limitations under the License.
  */
 
+mod config_overrides_generator;
 mod config_utils;
 mod marine_test_impl;
 mod glue_code_generator;
+mod host_imports_generator;
 mod module_generator;
+mod modules_builder;
 mod utils;
 
 pub use marine_test_impl::marine_test_impl;
+pub use modules_builder::build_modules;
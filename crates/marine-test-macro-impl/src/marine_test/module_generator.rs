@@ -17,10 +17,13 @@
 mod methods_generator;
 mod methods_generator_utils;
 mod record_type_generator;
+mod service_records;
 
 use crate::marine_test::utils;
 use crate::marine_test::config_utils::Module;
 use crate::TResult;
+use service_records::ServiceRecords;
+use service_records::SHARED_RECORDS_MODULE_NAME;
 
 use proc_macro2::TokenStream;
 use quote::quote;
@@ -41,12 +44,14 @@ use quote::quote;
 ///         pub fn greeting(&mut self, name: String) -> String {
 ///             use std::ops::DerefMut;
 ///             let arguments = marine_rs_sdk_test::internal::serde_json::json!([name]);
-///             let result = self
-///                 .marine
-///                 .as_ref
-///                 .borrow_mut()
-///                 .call_with_module_name("greeting", "greeting", arguments, <_>::default())
-///                 .expect("call to Marine failed");
+///             let result = marine_rs_sdk_test::internal::call_module(
+///                 &self.marine,
+///                 "greeting",
+///                 "greeting",
+///                 arguments,
+///                 <_>::default(),
+///             )
+///             .expect("call to Marine failed");
 ///             let result: String = marine_rs_sdk_test::internal::serde_json::from_value(result)
 ///                 .expect("the default deserializer shouldn't fail");
 ///             result
@@ -54,23 +59,81 @@ use quote::quote;
 ///     }
 /// }
 ///```
+///
+/// Definitions of all modules are placed into one module re-exported to the enclosing scope,
+/// records with the same name and definition in several modules are generated there once:
+///```ignore
+/// pub mod __m_generated_modules {
+///     pub mod __m_generated_records {
+///         pub struct User { pub name: String }
+///     }
+///
+///     pub mod greeting_structs {
+///         pub use __m_generated_greeting::*;
+///
+///         pub mod __m_generated_greeting {
+///             pub use super::super::__m_generated_records::User;
+///             // ...
+///         }
+///     }
+///
+///     // From conversions between same-named records of the same shape from different modules
+/// }
+///
+/// pub use __m_generated_modules::*;
+///```
 pub(super) fn generate_module_definitions<'i>(
     modules: impl ExactSizeIterator<Item = &'i Module<'i>>,
+    derives: &[syn::Path],
 ) -> TResult<Vec<TokenStream>> {
-    modules
-        .into_iter()
-        .map(generate_module_definition)
-        .collect::<TResult<Vec<_>>>()
+    let modules = modules.collect::<Vec<_>>();
+    let service_records = ServiceRecords::new(&modules)?;
+
+    let module_definitions = modules
+        .iter()
+        .map(|module| generate_module_definition(module, &service_records, derives))
+        .collect::<TResult<Vec<_>>>()?;
+    let shared_records = service_records.generate_shared_records(derives)?;
+    let conversions = service_records.generate_conversions()?;
+    let shared_records_ident = utils::new_ident(SHARED_RECORDS_MODULE_NAME)?;
+
+    let definitions = quote! {
+        // records differing between modules are deprecated to warn only where users refer to them
+        #[allow(deprecated)]
+        pub mod __m_generated_modules {
+            pub mod #shared_records_ident {
+                #(#shared_records)*
+            }
+
+            #(#module_definitions)*
+
+            #(#conversions)*
+        }
+
+        #[allow(unused_imports)]
+        pub use __m_generated_modules::*;
+    };
+
+    Ok(vec![definitions])
 }
 
-fn generate_module_definition(module: &Module<'_>) -> TResult<TokenStream> {
+fn generate_module_definition(
+    module: &Module<'_>,
+    service_records: &ServiceRecords<'_>,
+    derives: &[syn::Path],
+) -> TResult<TokenStream> {
     let module_name = module.name;
     let module_ident = utils::generate_module_ident(module_name)?;
     let structs_module_ident = utils::generate_structs_module_ident(module_name)?;
     let struct_ident = utils::generate_struct_name(module_name)?;
 
     let module_interface = &module.interface;
-    let module_records = record_type_generator::generate_records(&module_interface.record_types)?;
+    let module_records = record_type_generator::generate_records(
+        module_name,
+        &module_interface.record_types,
+        service_records,
+        derives,
+    )?;
     let module_functions = methods_generator::generate_module_methods(
         module_name,
         module_interface.function_signatures.iter(),
@@ -26,16 +26,27 @@ pub(super) fn generate_module_methods<'m, 'r>(
     records: &'r MRecordTypes,
 ) -> TResult<Vec<proc_macro2::TokenStream>> {
     use CallParametersSettings::*;
+    use ErrorHandlingSettings::*;
 
-    let methods_count = 2 * method_signatures.len();
+    let methods_count = 5 * method_signatures.len();
     method_signatures.try_fold::<_, _, TResult<_>>(
         Vec::with_capacity(methods_count),
         |mut methods, signature| {
-            let default_cp = generate_module_method(module_name, &signature, Default, records)?;
-            let user_cp = generate_module_method(module_name, &signature, UserDefined, records)?;
+            for &cp_setting in &[Default, UserDefined] {
+                for &error_setting in &[Panic, ReturnResult] {
+                    let method = generate_module_method(
+                        module_name,
+                        signature,
+                        cp_setting,
+                        error_setting,
+                        records,
+                    )?;
+                    methods.push(method);
+                }
+            }
 
-            methods.push(default_cp);
-            methods.push(user_cp);
+            let builder_method = generate_cp_builder_method(signature)?;
+            methods.push(builder_method);
 
             Ok(methods)
         },
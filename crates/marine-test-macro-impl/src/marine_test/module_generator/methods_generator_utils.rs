@@ -32,19 +32,34 @@ pub(super) enum CallParametersSettings {
     UserDefined,
 }
 
+/// Defines how errors of Marine calls and result deserialization are handled by a method.
+#[derive(Clone, Copy)]
+pub(super) enum ErrorHandlingSettings {
+    /// A method panics on errors and returns a function result as is.
+    Panic,
+    /// A method has the `try_` prefix and returns `Result<_, MarineTestError>`.
+    ReturnResult,
+}
+
 pub(super) fn generate_module_method(
     module_name: &str,
     signature: &MFunctionSignature,
     cp_setting: CallParametersSettings,
+    error_setting: ErrorHandlingSettings,
     records: &MRecordTypes,
 ) -> TResult<TokenStream> {
     let arguments = generate_arguments(signature.arguments.iter(), records)?;
-    let output_type = generate_output_type(&signature.outputs, records)?;
-    let mcall = generate_marine_call(module_name, cp_setting, &signature, records)?;
+    let output_type = generate_output_type(&signature.outputs, error_setting, records)?;
+    let mcall = generate_marine_call(module_name, cp_setting, error_setting, signature, records)?;
+
+    let func_name = match error_setting {
+        ErrorHandlingSettings::Panic => signature.name.to_string(),
+        ErrorHandlingSettings::ReturnResult => format!("try_{}", signature.name),
+    };
 
     let (cp, func_name) = match cp_setting {
         CallParametersSettings::Default => {
-            let func_name = new_ident(&signature.name)?;
+            let func_name = new_ident(&func_name)?;
             (TokenStream::new(), func_name)
         }
         CallParametersSettings::UserDefined => {
@@ -55,7 +70,7 @@ pub(super) fn generate_module_method(
             };
 
             let cp = quote! { #maybe_comma cp: marine_rs_sdk_test::CallParameters };
-            let func_name = format!("{}_cp", signature.name);
+            let func_name = format!("{}_cp", func_name);
             let func_name = new_ident(&func_name)?;
             (cp, func_name)
         }
@@ -70,9 +85,26 @@ pub(super) fn generate_module_method(
     Ok(module_method)
 }
 
+/// Generates a method returning `CallParametersBuilder` that knows argument names of the function,
+/// so tetraplets could be set by an argument name.
+pub(super) fn generate_cp_builder_method(signature: &MFunctionSignature) -> TResult<TokenStream> {
+    let func_name = format!("{}_cp_builder", signature.name);
+    let func_name = new_ident(&func_name)?;
+    let argument_names = signature.arguments.iter().map(|a| a.name.as_str());
+
+    let builder_method = quote! {
+        pub fn #func_name(&self) -> marine_rs_sdk_test::CallParametersBuilder {
+            marine_rs_sdk_test::CallParametersBuilder::new(&[#(#argument_names),*])
+        }
+    };
+
+    Ok(builder_method)
+}
+
 fn generate_marine_call(
     module_name: &str,
     cp_settings: CallParametersSettings,
+    error_setting: ErrorHandlingSettings,
     method_signature: &MFunctionSignature,
     records: &MRecordTypes,
 ) -> TResult<TokenStream> {
@@ -81,9 +113,15 @@ fn generate_marine_call(
 
     let output_type = get_output_type(&method_signature.outputs)?;
     let set_result = generate_set_result(&output_type);
-    let function_call = generate_function_call(module_name, &method_signature.name, cp_settings);
-    let convert_result_to_output_type = generate_convert_to_output(&output_type, records)?;
-    let ret = generate_ret(&output_type);
+    let function_call = generate_function_call(
+        module_name,
+        &method_signature.name,
+        cp_settings,
+        error_setting,
+    );
+    let convert_result_to_output_type =
+        generate_convert_to_output(&output_type, error_setting, records)?;
+    let ret = generate_ret(&output_type, error_setting);
 
     let function_call = quote! {
         use std::ops::DerefMut;
@@ -115,13 +153,21 @@ fn generate_function_call(
     module_name: &str,
     method_name: &str,
     cp_setting: CallParametersSettings,
+    error_setting: ErrorHandlingSettings,
 ) -> TokenStream {
     let cp = match cp_setting {
         CallParametersSettings::Default => quote! { <_>::default() },
         CallParametersSettings::UserDefined => quote! { cp },
     };
 
-    quote! { self.marine.as_ref().borrow_mut().call_module(#module_name, #method_name, arguments, #cp).expect("call to Marine failed"); }
+    let handle_error = match error_setting {
+        ErrorHandlingSettings::Panic => quote! { .expect("call to Marine failed") },
+        ErrorHandlingSettings::ReturnResult => quote! { ? },
+    };
+
+    quote! {
+        marine_rs_sdk_test::internal::call_module(&self.marine, #module_name, #method_name, arguments, #cp)#handle_error;
+    }
 }
 
 fn generate_set_result(output_type: &Option<&IType>) -> TokenStream {
@@ -133,13 +179,21 @@ fn generate_set_result(output_type: &Option<&IType>) -> TokenStream {
 
 fn generate_convert_to_output(
     output_type: &Option<&IType>,
+    error_setting: ErrorHandlingSettings,
     records: &MRecordTypes,
 ) -> TResult<TokenStream> {
     let result_stream = match output_type {
         Some(ty) => {
             let ty = itype_to_tokens(ty, records)?;
+            let handle_error = match error_setting {
+                ErrorHandlingSettings::Panic => {
+                    quote! { .expect("the default deserializer shouldn't fail") }
+                }
+                ErrorHandlingSettings::ReturnResult => quote! { ? },
+            };
+
             quote! {
-                let result: #ty = marine_rs_sdk_test::internal::serde_json::from_value(result).expect("the default deserializer shouldn't fail");
+                let result: #ty = marine_rs_sdk_test::internal::serde_json::from_value(result)#handle_error;
             }
         }
         None => TokenStream::new(),
@@ -148,10 +202,12 @@ fn generate_convert_to_output(
     Ok(result_stream)
 }
 
-fn generate_ret(output_type: &Option<&IType>) -> TokenStream {
-    match output_type {
-        Some(_) => quote! { result },
-        None => TokenStream::new(),
+fn generate_ret(output_type: &Option<&IType>, error_setting: ErrorHandlingSettings) -> TokenStream {
+    match (output_type, error_setting) {
+        (Some(_), ErrorHandlingSettings::Panic) => quote! { result },
+        (None, ErrorHandlingSettings::Panic) => TokenStream::new(),
+        (Some(_), ErrorHandlingSettings::ReturnResult) => quote! { Ok(result) },
+        (None, ErrorHandlingSettings::ReturnResult) => quote! { Ok(()) },
     }
 }
 
@@ -170,17 +226,25 @@ fn generate_arguments<'a, 'r>(
         .collect::<TResult<Vec<_>>>()
 }
 
-fn generate_output_type(output_types: &[IType], records: &MRecordTypes) -> TResult<TokenStream> {
-    let output_type = get_output_type(output_types)?;
-    match output_type {
-        None => Ok(TokenStream::new()),
-        Some(ty) => {
-            let output_type = itype_to_tokens(&ty, records)?;
-            let output_type = quote! { -> #output_type };
+fn generate_output_type(
+    output_types: &[IType],
+    error_setting: ErrorHandlingSettings,
+    records: &MRecordTypes,
+) -> TResult<TokenStream> {
+    let output_type = match get_output_type(output_types)? {
+        None => quote! { () },
+        Some(ty) => itype_to_tokens(ty, records)?,
+    };
 
-            Ok(output_type)
+    let output_type = match (output_types.is_empty(), error_setting) {
+        (true, ErrorHandlingSettings::Panic) => TokenStream::new(),
+        (false, ErrorHandlingSettings::Panic) => quote! { -> #output_type },
+        (_, ErrorHandlingSettings::ReturnResult) => {
+            quote! { -> std::result::Result<#output_type, marine_rs_sdk_test::MarineTestError> }
         }
-    }
+    };
+
+    Ok(output_type)
 }
 
 fn get_output_type(output_types: &[IType]) -> TResult<Option<&IType>> {
@@ -14,33 +14,142 @@
  * limitations under the License.
  */
 
+use super::service_records::ServiceRecords;
+use super::service_records::SHARED_RECORDS_MODULE_NAME;
+use crate::errors::CorruptedITSection;
 use crate::marine_test::utils;
 use crate::TResult;
 
 use marine_it_parser::interface::it::IRecordFieldType;
+use marine_it_parser::interface::it::IRecordType;
+use marine_it_parser::interface::it::IType;
 use marine_it_parser::interface::MRecordTypes;
 
 use proc_macro2::TokenStream;
 use quote::quote;
 
-pub(super) fn generate_records(records: &MRecordTypes) -> TResult<Vec<TokenStream>> {
+pub(super) fn generate_records(
+    module_name: &str,
+    records: &MRecordTypes,
+    service_records: &ServiceRecords<'_>,
+    derives: &[syn::Path],
+) -> TResult<Vec<TokenStream>> {
+    records
+        .values()
+        .map(|record| -> TResult<_> {
+            if service_records.is_shared(&record.name) {
+                let shared_module_ident = utils::new_ident(SHARED_RECORDS_MODULE_NAME)?;
+                let record_name_ident = utils::generate_record_name(&record.name)?;
+                return Ok(
+                    quote! { pub use super::super::#shared_module_ident::#record_name_ident; },
+                );
+            }
+
+            let deprecation_note = service_records.deprecation_note(module_name, &record.name)?;
+            generate_record(record, records, deprecation_note.as_deref(), derives)
+        })
+        .collect::<TResult<Vec<_>>>()
+}
+
+/// Generates a record definition, it's marked as deprecated if a note is provided to make
+/// the compiler show the note where the record is used. Besides the provided derives, records
+/// always derive `PartialEq` and `Default`, and `Eq` with `Hash` if fields allow it.
+pub(super) fn generate_record(
+    record: &IRecordType,
+    records: &MRecordTypes,
+    deprecation_note: Option<&str>,
+    derives: &[syn::Path],
+) -> TResult<TokenStream> {
     use std::ops::Deref;
 
-    records.iter().map(|(_, record)| -> TResult<_> {
-        let record_name_ident = utils::generate_record_name(&record.name)?;
-        let fields = prepare_field(record.fields.deref().iter(), records)?;
+    let record_name_ident = utils::generate_record_name(&record.name)?;
+    let fields = prepare_field(record.fields.deref().iter(), records)?;
+    let derives = prepare_derives(record, records, derives)?;
 
-        let generated_record = quote! {
-            #[derive(Clone, Debug, marine_rs_sdk_test::internal::serde::Serialize, marine_rs_sdk_test::internal::serde::Deserialize)]
-            #[serde(crate = "marine_rs_sdk_test::internal::serde")]
-            pub struct #record_name_ident {
-                #(pub #fields),*
-            }
+    #[cfg(feature = "property_testing")]
+    let arbitrary_impl =
+        generate_arbitrary_impl(&record_name_ident, record.fields.deref().iter(), records)?;
+    #[cfg(not(feature = "property_testing"))]
+    let arbitrary_impl = TokenStream::new();
+
+    let deprecation = match deprecation_note {
+        Some(note) => quote! { #[deprecated(note = #note)] },
+        None => TokenStream::new(),
+    };
+
+    let generated_record = quote! {
+        #deprecation
+        #[derive(Clone, Debug, PartialEq, Default, #(#derives,)* marine_rs_sdk_test::internal::serde::Serialize, marine_rs_sdk_test::internal::serde::Deserialize)]
+        #[serde(crate = "marine_rs_sdk_test::internal::serde")]
+        pub struct #record_name_ident {
+            #(pub #fields),*
+        }
+
+        #arbitrary_impl
+    };
+
+    Ok(generated_record)
+}
+
+/// Returns derives added to the always derived ones, requested derives that are derived anyway
+/// are skipped.
+fn prepare_derives(
+    record: &IRecordType,
+    records: &MRecordTypes,
+    derives: &[syn::Path],
+) -> TResult<Vec<TokenStream>> {
+    const DEFAULT_DERIVES: &[&str] = &["Clone", "Debug", "PartialEq", "Default"];
+    const EQ_DERIVES: &[&str] = &["Eq", "Hash"];
+
+    let mut prepared = Vec::new();
+    let supports_eq = supports_eq(record, records)?;
+    if supports_eq {
+        for derive in EQ_DERIVES {
+            let derive = utils::new_ident(derive)?;
+            prepared.push(quote! { #derive });
+        }
+    }
+
+    for derive in derives {
+        let name = match derive.segments.last() {
+            Some(segment) => segment.ident.to_string(),
+            None => continue,
         };
 
-        Ok(generated_record)
+        let is_derived = DEFAULT_DERIVES.contains(&name.as_str())
+            || (supports_eq && EQ_DERIVES.contains(&name.as_str()));
+        if !is_derived {
+            prepared.push(quote! { #derive });
+        }
+    }
+
+    Ok(prepared)
+}
+
+/// Returns true if all fields of a record, including fields of nested records,
+/// could derive `Eq` and `Hash`, i.e. there are no floats among them.
+fn supports_eq(record: &IRecordType, records: &MRecordTypes) -> TResult<bool> {
+    fn supports(ty: &IType, records: &MRecordTypes) -> TResult<bool> {
+        match ty {
+            IType::F32 | IType::F64 => Ok(false),
+            IType::Array(ty) => supports(ty, records),
+            IType::Record(record_id) => {
+                let record = records
+                    .get(record_id)
+                    .ok_or(CorruptedITSection::AbsentRecord(*record_id))?;
+                supports_eq(record, records)
+            }
+            _ => Ok(true),
+        }
+    }
+
+    for field in record.fields.iter() {
+        if !supports(&field.ty, records)? {
+            return Ok(false);
+        }
     }
-    ).collect::<TResult<Vec<_>>>()
+
+    Ok(true)
 }
 
 fn prepare_field<'f>(
@@ -58,3 +167,55 @@ fn prepare_field<'f>(
         })
         .collect::<TResult<Vec<_>>>()
 }
+
+/// Implements `proptest::arbitrary::Arbitrary` for a record. Strategies of fields are combined
+/// into nested pairs, because tuples implement `Strategy` only up to a limited length:
+///```ignore
+/// impl Arbitrary for User {
+///     type Parameters = ();
+///     type Strategy = BoxedStrategy<Self>;
+///
+///     fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+///         (any::<String>(), (any::<u32>(), Just(())))
+///             .prop_map(|(name, (age, ()))| User { name, age })
+///             .boxed()
+///     }
+/// }
+///```
+#[cfg(feature = "property_testing")]
+fn generate_arbitrary_impl<'f>(
+    record_name: &syn::Ident,
+    fields: impl DoubleEndedIterator<Item = &'f IRecordFieldType>,
+    records: &MRecordTypes,
+) -> TResult<TokenStream> {
+    let mut field_names = Vec::new();
+    let mut strategy = quote! { proptest::strategy::Just(()) };
+    let mut pattern = quote! { () };
+
+    for field in fields.rev() {
+        let field_name = utils::new_ident(&field.name)?;
+        let field_type = utils::itype_to_tokens(&field.ty, records)?;
+
+        strategy = quote! { (proptest::arbitrary::any::<#field_type>(), #strategy) };
+        pattern = quote! { (#field_name, #pattern) };
+        field_names.push(field_name);
+    }
+
+    let arbitrary_impl = quote! {
+        impl marine_rs_sdk_test::internal::proptest::arbitrary::Arbitrary for #record_name {
+            type Parameters = ();
+            type Strategy = marine_rs_sdk_test::internal::proptest::strategy::BoxedStrategy<Self>;
+
+            fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+                use marine_rs_sdk_test::internal::proptest;
+                use proptest::strategy::Strategy;
+
+                #strategy
+                    .prop_map(|#pattern| Self { #(#field_names),* })
+                    .boxed()
+            }
+        }
+    };
+
+    Ok(arbitrary_impl)
+}
@@ -0,0 +1,658 @@
+/*
+ * Copyright 2021 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::record_type_generator;
+use crate::marine_test::config_utils::Module;
+use crate::marine_test::utils;
+use crate::TResult;
+use crate::errors::CorruptedITSection;
+
+use marine_it_parser::interface::it::IRecordType;
+use marine_it_parser::interface::it::IType;
+use marine_it_parser::interface::MRecordTypes;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+/// Name of the module containing records shared by several modules of a service.
+pub(super) const SHARED_RECORDS_MODULE_NAME: &str = "__m_generated_records";
+
+/// Records of all modules of one service. A record having the same name and definition
+/// in each module it appears in is generated once and re-exported by these modules,
+/// records with the same name and shape in different modules get `From` conversions
+/// between each other, other same-named records are deprecated with a note describing
+/// the difference.
+pub(super) struct ServiceRecords<'m> {
+    occurrences: BTreeMap<&'m str, Vec<RecordOccurrence<'m>>>,
+    shared: BTreeSet<&'m str>,
+}
+
+#[derive(Clone, Copy)]
+struct RecordOccurrence<'m> {
+    module_name: &'m str,
+    record: &'m IRecordType,
+    records: &'m MRecordTypes,
+}
+
+impl<'m> ServiceRecords<'m> {
+    pub(super) fn new(modules: &[&'m Module<'m>]) -> TResult<Self> {
+        let mut occurrences = BTreeMap::<_, Vec<_>>::new();
+        for module in modules {
+            let records = &module.interface.record_types;
+            for record in records.values() {
+                let occurrence = RecordOccurrence {
+                    module_name: module.name,
+                    record,
+                    records,
+                };
+                occurrences
+                    .entry(record.name.as_str())
+                    .or_default()
+                    .push(occurrence);
+            }
+        }
+
+        // sort occurrences to make generated code independent of the HashMap order
+        for record_occurrences in occurrences.values_mut() {
+            record_occurrences.sort_by(|lhs, rhs| lhs.module_name.cmp(rhs.module_name));
+        }
+
+        let shared = collect_shared(&occurrences)?;
+
+        Ok(Self {
+            occurrences,
+            shared,
+        })
+    }
+
+    pub(super) fn is_shared(&self, record_name: &str) -> bool {
+        self.shared.contains(record_name)
+    }
+
+    /// Returns definitions of records shared by several modules.
+    pub(super) fn generate_shared_records(
+        &self,
+        derives: &[syn::Path],
+    ) -> TResult<Vec<TokenStream>> {
+        self.shared
+            .iter()
+            .map(|name| {
+                let occurrence = self.occurrences[name][0];
+                record_type_generator::generate_record(
+                    occurrence.record,
+                    occurrence.records,
+                    None,
+                    derives,
+                )
+            })
+            .collect::<TResult<Vec<_>>>()
+    }
+
+    /// Returns a note describing how a record differs from records of other modules with
+    /// the same name, if they can't be converted to each other.
+    pub(super) fn deprecation_note(
+        &self,
+        module_name: &str,
+        record_name: &str,
+    ) -> TResult<Option<String>> {
+        let occurrences = match self.occurrences.get(record_name) {
+            Some(occurrences) if !self.is_shared(record_name) => occurrences,
+            _ => return Ok(None),
+        };
+
+        let this = match occurrences.iter().find(|o| o.module_name == module_name) {
+            Some(this) => *this,
+            None => return Ok(None),
+        };
+
+        let mut differences = Vec::new();
+        for other in occurrences.iter().filter(|o| o.module_name != module_name) {
+            if let Some(difference) = shape_difference(this, *other)? {
+                differences.push(format!(
+                    "it differs from `{}` of module `{}`: {}",
+                    record_name, other.module_name, difference
+                ));
+            }
+        }
+
+        if differences.is_empty() {
+            return Ok(None);
+        }
+
+        let note = format!(
+            "`{}` of module `{}` can't be passed to other modules as is, {}",
+            record_name,
+            module_name,
+            differences.join("; ")
+        );
+        Ok(Some(note))
+    }
+
+    /// Returns `From` implementations for each pair of records with the same name and shape
+    /// from different modules. Records are referred relatively to the module containing
+    /// all generated modules.
+    pub(super) fn generate_conversions(&self) -> TResult<Vec<TokenStream>> {
+        let mut conversions = Vec::new();
+        for (&record_name, occurrences) in &self.occurrences {
+            if self.is_shared(record_name) {
+                continue;
+            }
+
+            for (lhs_id, lhs) in occurrences.iter().enumerate() {
+                for rhs in occurrences.iter().skip(lhs_id + 1) {
+                    if !same_record_shape(lhs.record, lhs.records, rhs.record, rhs.records)? {
+                        continue;
+                    }
+
+                    let lhs_path = record_path(lhs.module_name, record_name)?;
+                    let rhs_path = record_path(rhs.module_name, record_name)?;
+                    conversions.push(generate_conversion(&lhs_path, &rhs_path, rhs.record)?);
+                    conversions.push(generate_conversion(&rhs_path, &lhs_path, lhs.record)?);
+                }
+            }
+        }
+
+        Ok(conversions)
+    }
+}
+
+fn record_path(module_name: &str, record_name: &str) -> TResult<TokenStream> {
+    let module_ident = utils::generate_structs_module_ident(module_name)?;
+    let record_ident = utils::generate_record_name(record_name)?;
+
+    Ok(quote! { #module_ident::#record_ident })
+}
+
+/// Returns names of records that could be shared. Such records are present in several modules,
+/// have the same definition in all of them and refer only to records that are shared as well.
+fn collect_shared<'m>(
+    occurrences: &BTreeMap<&'m str, Vec<RecordOccurrence<'m>>>,
+) -> TResult<BTreeSet<&'m str>> {
+    let mut shared = BTreeSet::new();
+    for (&record_name, record_occurrences) in occurrences {
+        if record_occurrences.len() < 2 {
+            continue;
+        }
+
+        let first = record_occurrences[0];
+        let mut is_same = true;
+        for other in &record_occurrences[1..] {
+            is_same &= same_definition(first, *other)?;
+        }
+
+        if is_same {
+            shared.insert(record_name);
+        }
+    }
+
+    loop {
+        let mut unshared = Vec::new();
+        for &record_name in &shared {
+            let occurrence = occurrences[record_name][0];
+            for nested_name in nested_records(occurrence)? {
+                if !shared.contains(nested_name) {
+                    unshared.push(record_name);
+                    break;
+                }
+            }
+        }
+
+        if unshared.is_empty() {
+            return Ok(shared);
+        }
+
+        for record_name in unshared {
+            shared.remove(record_name);
+        }
+    }
+}
+
+/// Returns true if records have the same fields, nested records are compared by names.
+fn same_definition(lhs: RecordOccurrence<'_>, rhs: RecordOccurrence<'_>) -> TResult<bool> {
+    if lhs.record.fields.len() != rhs.record.fields.len() {
+        return Ok(false);
+    }
+
+    for (lhs_field, rhs_field) in lhs.record.fields.iter().zip(rhs.record.fields.iter()) {
+        if lhs_field.name != rhs_field.name
+            || !same_type(&lhs_field.ty, lhs.records, &rhs_field.ty, rhs.records)?
+        {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+fn same_type(
+    lhs: &IType,
+    lhs_records: &MRecordTypes,
+    rhs: &IType,
+    rhs_records: &MRecordTypes,
+) -> TResult<bool> {
+    match (lhs, rhs) {
+        (IType::Record(lhs_id), IType::Record(rhs_id)) => {
+            let lhs_record = get_record(*lhs_id, lhs_records)?;
+            let rhs_record = get_record(*rhs_id, rhs_records)?;
+            Ok(lhs_record.name == rhs_record.name)
+        }
+        (IType::Array(lhs), IType::Array(rhs)) => same_type(lhs, lhs_records, rhs, rhs_records),
+        (lhs, rhs) => Ok(lhs == rhs),
+    }
+}
+
+/// Returns true if records have fields with the same names and types, nested records must
+/// have the same names and shapes as well, so they could be converted by their own `From`.
+fn same_record_shape(
+    lhs: &IRecordType,
+    lhs_records: &MRecordTypes,
+    rhs: &IRecordType,
+    rhs_records: &MRecordTypes,
+) -> TResult<bool> {
+    if lhs.fields.len() != rhs.fields.len() {
+        return Ok(false);
+    }
+
+    for (lhs_field, rhs_field) in lhs.fields.iter().zip(rhs.fields.iter()) {
+        if lhs_field.name != rhs_field.name
+            || !same_shape(&lhs_field.ty, lhs_records, &rhs_field.ty, rhs_records)?
+        {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+fn same_shape(
+    lhs: &IType,
+    lhs_records: &MRecordTypes,
+    rhs: &IType,
+    rhs_records: &MRecordTypes,
+) -> TResult<bool> {
+    match (lhs, rhs) {
+        (IType::Record(lhs_id), IType::Record(rhs_id)) => {
+            let lhs_record = get_record(*lhs_id, lhs_records)?;
+            let rhs_record = get_record(*rhs_id, rhs_records)?;
+            if lhs_record.name != rhs_record.name {
+                return Ok(false);
+            }
+
+            same_record_shape(lhs_record, lhs_records, rhs_record, rhs_records)
+        }
+        (IType::Array(lhs), IType::Array(rhs)) => same_shape(lhs, lhs_records, rhs, rhs_records),
+        (lhs, rhs) => Ok(lhs == rhs),
+    }
+}
+
+/// Describes the first found difference between shapes of two records.
+fn shape_difference(
+    this: RecordOccurrence<'_>,
+    other: RecordOccurrence<'_>,
+) -> TResult<Option<String>> {
+    let this_fields = &this.record.fields;
+    let other_fields = &other.record.fields;
+    if this_fields.len() != other_fields.len() {
+        let difference = format!(
+            "it has {} fields instead of {}",
+            this_fields.len(),
+            other_fields.len()
+        );
+        return Ok(Some(difference));
+    }
+
+    for (this_field, other_field) in this_fields.iter().zip(other_fields.iter()) {
+        if this_field.name != other_field.name {
+            let difference = format!(
+                "field `{}` is named `{}` there",
+                this_field.name, other_field.name
+            );
+            return Ok(Some(difference));
+        }
+
+        if same_shape(&this_field.ty, this.records, &other_field.ty, other.records)? {
+            continue;
+        }
+
+        let this_type = type_name(&this_field.ty, this.records)?;
+        let other_type = type_name(&other_field.ty, other.records)?;
+        let difference = if this_type == other_type {
+            format!(
+                "field `{}` has type `{}` of a different shape",
+                this_field.name, this_type
+            )
+        } else {
+            format!(
+                "field `{}` has type `{}` instead of `{}`",
+                this_field.name, this_type, other_type
+            )
+        };
+        return Ok(Some(difference));
+    }
+
+    Ok(None)
+}
+
+fn nested_records<'m>(occurrence: RecordOccurrence<'m>) -> TResult<Vec<&'m str>> {
+    fn collect<'m>(ty: &IType, records: &'m MRecordTypes, names: &mut Vec<&'m str>) -> TResult<()> {
+        match ty {
+            IType::Record(record_id) => names.push(get_record(*record_id, records)?.name.as_str()),
+            IType::Array(ty) => collect(ty, records, names)?,
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    let mut names = Vec::new();
+    for field in occurrence.record.fields.iter() {
+        collect(&field.ty, occurrence.records, &mut names)?;
+    }
+
+    Ok(names)
+}
+
+fn type_name(ty: &IType, records: &MRecordTypes) -> TResult<String> {
+    let name = match ty {
+        IType::Record(record_id) => get_record(*record_id, records)?.name.clone(),
+        IType::Array(ty) => format!("Vec<{}>", type_name(ty, records)?),
+        IType::ByteArray => "Vec<u8>".to_string(),
+        ty => utils::itype_to_tokens(ty, records)?.to_string(),
+    };
+
+    Ok(name)
+}
+
+fn get_record(record_id: u64, records: &MRecordTypes) -> TResult<&IRecordType> {
+    records
+        .get(&record_id)
+        .map(|record| record.as_ref())
+        .ok_or_else(|| CorruptedITSection::AbsentRecord(record_id).into())
+}
+
+/// Generates the following conversion, nested records are converted by their own `From`:
+///```ignore
+/// impl From<greeting_structs::User> for storage_structs::User {
+///     fn from(value: greeting_structs::User) -> Self {
+///         Self {
+///             name: value.name,
+///             friends: value.friends.into_iter().map(|value| value.into()).collect(),
+///         }
+///     }
+/// }
+///```
+fn generate_conversion(
+    from_path: &TokenStream,
+    to_path: &TokenStream,
+    to_record: &IRecordType,
+) -> TResult<TokenStream> {
+    let fields = to_record
+        .fields
+        .iter()
+        .map(|field| -> TResult<_> {
+            let field_ident = utils::new_ident(&field.name)?;
+            let value = convert_value(quote! { value.#field_ident }, &field.ty);
+            Ok(quote! { #field_ident: #value })
+        })
+        .collect::<TResult<Vec<_>>>()?;
+
+    let conversion = quote! {
+        impl From<#from_path> for #to_path {
+            fn from(value: #from_path) -> Self {
+                Self {
+                    #(#fields),*
+                }
+            }
+        }
+    };
+
+    Ok(conversion)
+}
+
+fn convert_value(value: TokenStream, ty: &IType) -> TokenStream {
+    match ty {
+        IType::Record(_) => quote! { #value.into() },
+        IType::Array(ty) if contains_record(ty) => {
+            let converted = convert_value(quote! { value }, ty);
+            quote! { #value.into_iter().map(|value| #converted).collect() }
+        }
+        _ => value,
+    }
+}
+
+fn contains_record(ty: &IType) -> bool {
+    match ty {
+        IType::Record(_) => true,
+        IType::Array(ty) => contains_record(ty),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ServiceRecords;
+    use crate::marine_test::config_utils::Module;
+
+    use marine_it_parser::interface::it::IRecordFieldType;
+    use marine_it_parser::interface::it::IRecordType;
+    use marine_it_parser::interface::it::IType;
+    use marine_it_parser::interface::MModuleInterface;
+    use wasmer_it::NEVec;
+
+    use std::path::PathBuf;
+    use std::rc::Rc;
+
+    fn record(name: &str, fields: Vec<(&str, IType)>) -> IRecordType {
+        let fields = fields
+            .into_iter()
+            .map(|(name, ty)| IRecordFieldType {
+                name: name.to_string(),
+                ty,
+            })
+            .collect();
+
+        IRecordType {
+            name: name.to_string(),
+            fields: NEVec::new(fields).unwrap(),
+        }
+    }
+
+    fn module(name: &'static str, records: Vec<(u64, IRecordType)>) -> Module<'static> {
+        let record_types = records
+            .into_iter()
+            .map(|(id, record)| (id, Rc::new(record)))
+            .collect();
+
+        Module {
+            name,
+            path: PathBuf::from(format!("{}.wasm", name)),
+            interface: MModuleInterface {
+                record_types,
+                function_signatures: Vec::new(),
+            },
+        }
+    }
+
+    fn user(fields: Vec<(&str, IType)>) -> Vec<(u64, IRecordType)> {
+        vec![(0, record("User", fields))]
+    }
+
+    fn conversions(service_records: &ServiceRecords<'_>) -> Vec<String> {
+        service_records
+            .generate_conversions()
+            .unwrap()
+            .iter()
+            .map(|conversion| conversion.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn identical_records_are_shared() {
+        let modules = [
+            module("users", user(vec![("name", IType::String)])),
+            module("profiles", user(vec![("name", IType::String)])),
+        ];
+        let modules = modules.iter().collect::<Vec<_>>();
+
+        let service_records = ServiceRecords::new(&modules).unwrap();
+
+        assert!(service_records.is_shared("User"));
+        assert!(conversions(&service_records).is_empty());
+        assert_eq!(
+            service_records.deprecation_note("users", "User").unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn records_of_one_module_are_not_shared() {
+        let modules = [
+            module("users", user(vec![("name", IType::String)])),
+            module("profiles", Vec::new()),
+        ];
+        let modules = modules.iter().collect::<Vec<_>>();
+
+        let service_records = ServiceRecords::new(&modules).unwrap();
+
+        assert!(!service_records.is_shared("User"));
+        assert!(conversions(&service_records).is_empty());
+        assert_eq!(
+            service_records.deprecation_note("users", "User").unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn records_referring_unshared_records_are_converted() {
+        let address = |fields| (1, record("Address", fields));
+        let user = (0, record("User", vec![("address", IType::Record(1))]));
+        let modules = [
+            module(
+                "users",
+                vec![user.clone(), address(vec![("city", IType::String)])],
+            ),
+            module(
+                "profiles",
+                vec![user.clone(), address(vec![("city", IType::String)])],
+            ),
+            module("geo", vec![address(vec![("city", IType::U32)])]),
+        ];
+        let modules = modules.iter().collect::<Vec<_>>();
+
+        let service_records = ServiceRecords::new(&modules).unwrap();
+
+        assert!(!service_records.is_shared("User"));
+        assert!(!service_records.is_shared("Address"));
+
+        let conversions = conversions(&service_records);
+        let expected_conversions = [
+            "impl From < profiles_structs :: Address > for users_structs :: Address",
+            "impl From < users_structs :: Address > for profiles_structs :: Address",
+            "impl From < profiles_structs :: User > for users_structs :: User",
+            "impl From < users_structs :: User > for profiles_structs :: User",
+        ];
+        assert_eq!(conversions.len(), expected_conversions.len());
+        for (conversion, expected_conversion) in conversions.iter().zip(expected_conversions.iter())
+        {
+            assert!(
+                conversion.starts_with(expected_conversion),
+                "{}",
+                conversion
+            );
+        }
+
+        assert_eq!(
+            service_records.deprecation_note("users", "User").unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn differing_records_are_deprecated() {
+        let modules = [
+            module("users", user(vec![("name", IType::String)])),
+            module("profiles", user(vec![("name", IType::String)])),
+            module("auth", user(vec![("name", IType::ByteArray)])),
+        ];
+        let modules = modules.iter().collect::<Vec<_>>();
+
+        let service_records = ServiceRecords::new(&modules).unwrap();
+
+        assert!(!service_records.is_shared("User"));
+        assert_eq!(conversions(&service_records).len(), 2);
+
+        let note = service_records.deprecation_note("auth", "User").unwrap();
+        let expected_note = "`User` of module `auth` can't be passed to other modules as is, \
+            it differs from `User` of module `profiles`: field `name` has type `Vec<u8>` instead of `String`; \
+            it differs from `User` of module `users`: field `name` has type `Vec<u8>` instead of `String`";
+        assert_eq!(note.as_deref(), Some(expected_note));
+
+        let note = service_records.deprecation_note("users", "User").unwrap();
+        let expected_note = "`User` of module `users` can't be passed to other modules as is, \
+            it differs from `User` of module `auth`: field `name` has type `String` instead of `Vec<u8>`";
+        assert_eq!(note.as_deref(), Some(expected_note));
+    }
+
+    #[test]
+    fn records_with_different_names_are_not_converted() {
+        let modules = [
+            module("users", user(vec![("name", IType::String)])),
+            module(
+                "profiles",
+                vec![(0, record("Profile", vec![("name", IType::String)]))],
+            ),
+        ];
+        let modules = modules.iter().collect::<Vec<_>>();
+
+        let service_records = ServiceRecords::new(&modules).unwrap();
+
+        assert!(conversions(&service_records).is_empty());
+    }
+
+    #[test]
+    fn records_referring_differently_named_records_are_deprecated() {
+        let modules = [
+            module(
+                "users",
+                vec![
+                    (0, record("User", vec![("home", IType::Record(1))])),
+                    (1, record("Address", vec![("city", IType::String)])),
+                ],
+            ),
+            module(
+                "profiles",
+                vec![
+                    (0, record("User", vec![("home", IType::Record(1))])),
+                    (1, record("Location", vec![("city", IType::String)])),
+                ],
+            ),
+        ];
+        let modules = modules.iter().collect::<Vec<_>>();
+
+        let service_records = ServiceRecords::new(&modules).unwrap();
+
+        assert!(!service_records.is_shared("User"));
+        assert!(conversions(&service_records).is_empty());
+
+        let note = service_records.deprecation_note("users", "User").unwrap();
+        let expected_note = "`User` of module `users` can't be passed to other modules as is, \
+            it differs from `User` of module `profiles`: field `home` has type `Address` instead of `Location`";
+        assert_eq!(note.as_deref(), Some(expected_note));
+    }
+}
@@ -0,0 +1,395 @@
+/*
+ * Copyright 2021 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::marine_test::config_utils;
+use crate::TResult;
+use crate::TestGeneratorError;
+
+use fluence_app_service::TomlAppServiceConfig;
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::SystemTime;
+
+const WASM_TARGET: &str = "wasm32-wasi";
+const BUILD_TARGET_DIR: &str = "marine_test_target";
+
+/// Rebuilds modules which crate sources are newer than compiled modules and copies them
+/// to modules_dir. It's intended to be called from a build script of a crate with tests,
+/// so modules are built before tests and `marine_test` only checks that they are up to date:
+///```ignore
+/// fn main() {
+///     marine_rs_sdk_test::build_modules("Config.toml", None, &[("greeting", "../greeting")])
+///         .expect("modules can't be built");
+/// }
+///```
+///
+/// Paths are relative to the crate root, modules_dir is taken from the config if it isn't
+/// specified. `cargo:rerun-if-changed` is printed for the config and crates of modules.
+/// Crates are built with a target dir inside `OUT_DIR` of the build script, so the build doesn't
+/// wait for the cargo lock held by a build of the tests and doesn't race with other projects.
+pub fn build_modules(
+    config_path: &str,
+    modules_dir: Option<&str>,
+    crates: &[(&str, &str)],
+) -> TResult<()> {
+    println!("cargo:rerun-if-changed={}", config_path);
+
+    let config = TomlAppServiceConfig::load(config_path)?;
+    let modules_dir = modules_dir.map(ToString::to_string);
+    let modules_dir = match config_utils::resolve_modules_dir(&config, modules_dir) {
+        Some(modules_dir) => modules_dir,
+        None => return Err(TestGeneratorError::ModulesDirUnspecified),
+    };
+    let module_paths = config_utils::collect_module_paths(&config, modules_dir.clone());
+
+    for &(module_name, crate_path) in crates {
+        println!("cargo:rerun-if-changed={}", crate_path);
+
+        let module_path = find_module_path(&module_paths, module_name)?;
+        build_module(Path::new(crate_path), &modules_dir, module_path).map_err(|reason| {
+            TestGeneratorError::ModuleBuildFailed {
+                module_name: module_name.to_string(),
+                reason,
+            }
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Checks that modules are newer than sources of their crates, so tests don't run with
+/// outdated modules. Modules themselves are built by `build_modules` called from a build script.
+pub(super) fn check_modules(
+    config: &TomlAppServiceConfig,
+    modules_dir: &Path,
+    crates: HashMap<String, String>,
+    file_path: &Path,
+) -> TResult<()> {
+    if crates.is_empty() {
+        return Ok(());
+    }
+
+    let module_paths = config_utils::collect_module_paths(config, modules_dir.to_path_buf());
+
+    // sort crates to report errors in the same order each time
+    let mut crates = crates.into_iter().collect::<Vec<_>>();
+    crates.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+
+    for (module_name, crate_path) in crates {
+        let module_path = find_module_path(&module_paths, &module_name)?;
+        let crate_path = file_path.join(crate_path);
+
+        match is_module_stale(&crate_path, module_path) {
+            Ok(false) => {}
+            Ok(true) => return Err(TestGeneratorError::StaleModule(module_name)),
+            Err(e) => {
+                return Err(TestGeneratorError::ModuleCheckFailed {
+                    module_name,
+                    reason: e.to_string(),
+                })
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn find_module_path<'p>(
+    module_paths: &'p [(&str, PathBuf)],
+    module_name: &str,
+) -> TResult<&'p Path> {
+    module_paths
+        .iter()
+        .find(|(name, _)| *name == module_name)
+        .map(|(_, module_path)| module_path.as_path())
+        .ok_or_else(|| TestGeneratorError::UnknownBuiltModule(module_name.to_string()))
+}
+
+fn build_module(crate_path: &Path, modules_dir: &Path, module_path: &Path) -> Result<(), String> {
+    let is_stale = is_module_stale(crate_path, module_path)
+        .map_err(|e| format!("can't check whether the module is stale: {}", e))?;
+    if !is_stale {
+        return Ok(());
+    }
+
+    let built_module_path = build_crate(crate_path)?;
+
+    std::fs::create_dir_all(modules_dir)
+        .and_then(|_| std::fs::copy(&built_module_path, module_path))
+        .map_err(|e| {
+            format!(
+                "can't copy {:?} to {:?}: {}",
+                built_module_path, module_path, e
+            )
+        })?;
+
+    Ok(())
+}
+
+/// Returns true if the compiled module is absent or older than any file of its crate.
+fn is_module_stale(crate_path: &Path, module_path: &Path) -> io::Result<bool> {
+    let module_modified = match std::fs::metadata(module_path) {
+        Ok(metadata) => metadata.modified()?,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(true),
+        Err(e) => return Err(e),
+    };
+
+    let sources_modified = latest_modification_time(crate_path)?;
+    Ok(sources_modified > module_modified)
+}
+
+/// Returns the latest modification time of files in the provided dir skipping hidden
+/// and target dirs.
+fn latest_modification_time(dir: &Path) -> io::Result<SystemTime> {
+    let mut latest_modified = SystemTime::UNIX_EPOCH;
+
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+
+        let modified = if metadata.is_dir() {
+            let dir_name = entry.file_name();
+            let dir_name = dir_name.to_string_lossy();
+            if dir_name == "target" || dir_name.starts_with('.') {
+                continue;
+            }
+
+            latest_modification_time(&entry.path())?
+        } else {
+            metadata.modified()?
+        };
+
+        latest_modified = std::cmp::max(latest_modified, modified);
+    }
+
+    Ok(latest_modified)
+}
+
+/// Builds the crate by `marine build` and returns a path to the compiled module.
+fn build_crate(crate_path: &Path) -> Result<PathBuf, String> {
+    let bin_name = crate_bin_name(crate_path)?;
+    // OUT_DIR is set only for build scripts, otherwise the crate is built into its own target dir
+    let target_dir = match std::env::var_os("OUT_DIR") {
+        Some(out_dir) => PathBuf::from(out_dir).join(BUILD_TARGET_DIR),
+        None => crate_path.join("target"),
+    };
+
+    let output = Command::new("marine")
+        .arg("build")
+        .arg("--release")
+        .current_dir(crate_path)
+        .env("CARGO_TARGET_DIR", &target_dir)
+        .output()
+        .map_err(|e| {
+            format!(
+                "marine can't be run, please install it by `cargo install marine`: {}",
+                e
+            )
+        })?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    let module_path = target_dir
+        .join(WASM_TARGET)
+        .join("release")
+        .join(bin_name)
+        .with_extension("wasm");
+
+    Ok(module_path)
+}
+
+/// Returns a name of the first binary target of the crate obtained by `cargo metadata`.
+fn crate_bin_name(crate_path: &Path) -> Result<String, String> {
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo"));
+    let output = Command::new(cargo)
+        .arg("metadata")
+        .arg("--no-deps")
+        .arg("--format-version")
+        .arg("1")
+        .current_dir(crate_path)
+        .output()
+        .map_err(|e| format!("cargo metadata can't be run: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    let metadata: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("cargo metadata returned invalid json: {}", e))?;
+
+    let manifest_path = crate_path
+        .join("Cargo.toml")
+        .canonicalize()
+        .map_err(|e| format!("crate manifest can't be found: {}", e))?;
+
+    bin_name_from_metadata(&metadata, &manifest_path)
+        .ok_or_else(|| format!("crate at {:?} doesn't have a binary target", crate_path))
+}
+
+/// Returns a name of the first binary target of the package with the provided manifest,
+/// all packages of a workspace are returned by `cargo metadata`, so the package is found
+/// by its manifest path.
+fn bin_name_from_metadata(metadata: &serde_json::Value, manifest_path: &Path) -> Option<String> {
+    metadata["packages"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|package| package["manifest_path"].as_str().map(Path::new) == Some(manifest_path))
+        .flat_map(|package| package["targets"].as_array().into_iter().flatten())
+        .find(|target| {
+            target["kind"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .any(|kind| kind == "bin")
+        })
+        .and_then(|target| target["name"].as_str())
+        .map(ToString::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::bin_name_from_metadata;
+    use super::is_module_stale;
+    use super::latest_modification_time;
+
+    use serde_json::json;
+
+    use std::path::Path;
+    use std::path::PathBuf;
+
+    struct TestDir(PathBuf);
+
+    impl TestDir {
+        fn new(name: &str) -> Self {
+            let path =
+                std::env::temp_dir().join(format!("marine_test_{}_{}", name, std::process::id()));
+            let _ = std::fs::remove_dir_all(&path);
+            std::fs::create_dir_all(&path).unwrap();
+
+            Self(path)
+        }
+
+        fn write(&self, file_path: &str) -> PathBuf {
+            let file_path = self.0.join(file_path);
+            std::fs::create_dir_all(file_path.parent().unwrap()).unwrap();
+            std::fs::write(&file_path, file_path.to_string_lossy().as_bytes()).unwrap();
+
+            file_path
+        }
+    }
+
+    impl Drop for TestDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn modified(path: &Path) -> std::time::SystemTime {
+        std::fs::metadata(path).unwrap().modified().unwrap()
+    }
+
+    /// Rewrites the file until its modification time is after the provided file's one,
+    /// because file systems could have coarse time resolution.
+    fn write_after(dir: &TestDir, file_path: &str, previous: &Path) -> PathBuf {
+        loop {
+            let file_path = dir.write(file_path);
+            if modified(&file_path) > modified(previous) {
+                return file_path;
+            }
+
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn absent_module_is_stale() {
+        let dir = TestDir::new("absent_module");
+        dir.write("greeting/src/main.rs");
+
+        let is_stale = is_module_stale(&dir.0.join("greeting"), &dir.0.join("greeting.wasm"));
+        assert!(is_stale.unwrap());
+    }
+
+    #[test]
+    fn module_is_stale_after_sources_change() {
+        let dir = TestDir::new("changed_sources");
+        let source_path = dir.write("greeting/src/main.rs");
+        let module_path = write_after(&dir, "greeting.wasm", &source_path);
+
+        let crate_path = dir.0.join("greeting");
+        assert!(!is_module_stale(&crate_path, &module_path).unwrap());
+
+        write_after(&dir, "greeting/src/main.rs", &module_path);
+        assert!(is_module_stale(&crate_path, &module_path).unwrap());
+    }
+
+    #[test]
+    fn target_and_hidden_dirs_are_skipped() {
+        let dir = TestDir::new("skipped_dirs");
+        let source_path = dir.write("greeting/src/main.rs");
+        let target_path = write_after(&dir, "greeting/target/release/greeting", &source_path);
+        write_after(&dir, "greeting/.git/index", &target_path);
+
+        let latest_modified = latest_modification_time(&dir.0.join("greeting")).unwrap();
+        assert_eq!(latest_modified, modified(&source_path));
+    }
+
+    #[test]
+    fn bin_name_of_workspace_member() {
+        let metadata = json!({
+            "packages": [
+                {
+                    "manifest_path": "/workspace/other/Cargo.toml",
+                    "targets": [{ "kind": ["bin"], "name": "other" }]
+                },
+                {
+                    "manifest_path": "/workspace/greeting/Cargo.toml",
+                    "targets": [
+                        { "kind": ["lib"], "name": "greeting_lib" },
+                        { "kind": ["bin"], "name": "greeting_bin" }
+                    ]
+                }
+            ]
+        });
+
+        let manifest_path = Path::new("/workspace/greeting/Cargo.toml");
+        let bin_name = bin_name_from_metadata(&metadata, manifest_path);
+        assert_eq!(bin_name.as_deref(), Some("greeting_bin"));
+    }
+
+    #[test]
+    fn bin_name_of_crate_without_binary() {
+        let metadata = json!({
+            "packages": [
+                {
+                    "manifest_path": "/workspace/greeting/Cargo.toml",
+                    "targets": [{ "kind": ["lib"], "name": "greeting" }]
+                }
+            ]
+        });
+
+        let manifest_path = Path::new("/workspace/greeting/Cargo.toml");
+        assert_eq!(bin_name_from_metadata(&metadata, manifest_path), None);
+    }
+}
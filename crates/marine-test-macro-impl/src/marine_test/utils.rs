@@ -80,3 +80,32 @@ pub(super) fn itype_to_tokens(itype: &IType, records: &MRecordTypes) -> TResult<
 
     Ok(token_stream)
 }
+
+/// Generates an expression constructing the provided IType, unlike itype_to_tokens that
+/// generates the corresponding Rust type.
+pub(super) fn itype_to_value_tokens(itype: &IType) -> TokenStream {
+    let variant = match itype {
+        IType::Record(record_id) => quote! { Record(#record_id) },
+        IType::Array(ty) => {
+            let inner_ty = itype_to_value_tokens(ty);
+            quote! { Array(Box::new(#inner_ty)) }
+        }
+        IType::String => quote! { String },
+        IType::ByteArray => quote! { ByteArray },
+        IType::Boolean => quote! { Boolean },
+        IType::S8 => quote! { S8 },
+        IType::S16 => quote! { S16 },
+        IType::S32 => quote! { S32 },
+        IType::S64 => quote! { S64 },
+        IType::U8 => quote! { U8 },
+        IType::U16 => quote! { U16 },
+        IType::U32 => quote! { U32 },
+        IType::U64 => quote! { U64 },
+        IType::I32 => quote! { I32 },
+        IType::I64 => quote! { I64 },
+        IType::F32 => quote! { F32 },
+        IType::F64 => quote! { F64 },
+    };
+
+    quote! { marine_rs_sdk_test::internal::IType::#variant }
+}
@@ -41,6 +41,123 @@ use syn::spanned::Spanned;
 ///     assert_eq!(&service_result, "Hi, name!");
 /// }
 ///```
+///
+/// Several services could be tested together, each of them is available by its name:
+///```rust
+/// #[marine_test(services(
+///     auth(config_path = "/path/to/auth/Config.toml"),
+///     storage(config_path = "/path/to/storage/Config.toml", modules_dir = "path/to/storage/modules")
+/// ))]
+/// fn test() {
+///     let token = auth.auth.get_token("John".to_string());
+///     let value = storage.storage.get("key".to_string(), token);
+/// }
+///```
+///
+/// Host imports of modules could be replaced by closures or functions, argument and return
+/// types are taken from signatures of the imported functions. Imports are looked up in the `host`
+/// namespace, another one could be specified by `host_imports_namespace = "namespace"`:
+///```rust
+/// #[marine_test(
+///     config_path = "/path/to/Config.toml",
+///     host_imports(curl_adapter(curl_request = "|args: Vec<String>| fake_curl_result(args)"))
+/// )]
+/// fn test() {
+///     let result = curl_adapter.download("https://fluence.network".to_string());
+/// }
+///```
+///
+/// Modules could be rebuilt by `marine_rs_sdk_test::build_modules` called from a build script,
+/// then the `build` attribute makes compilation fail if a module is older than files of its crate:
+///```rust
+/// // build.rs
+/// fn main() {
+///     marine_rs_sdk_test::build_modules("Config.toml", None, &[("greeting", "../greeting")])
+///         .expect("modules can't be built");
+/// }
+///
+/// // tests
+/// #[marine_test(config_path = "../Config.toml", build(greeting = "../../greeting"))]
+/// fn test() {
+///     let service_result = greeting.greeting("John".to_string());
+/// }
+///```
+///
+/// Module configs could be overridden for a test without a separate copy of `Config.toml`,
+/// envs, mapped dirs and mounted binaries are added to ones from the config:
+///```rust
+/// #[marine_test(
+///     config_path = "/path/to/Config.toml",
+///     config_overrides(greeting(
+///         envs(LOG_LEVEL = "debug"),
+///         mapped_dirs(tmp = "/tmp"),
+///         mounted_binaries(curl = "/usr/bin/curl"),
+///         mem_pages_count = 100,
+///         logger_enabled = true,
+///         logging_mask = 3
+///     ))
+/// )]
+/// fn test() {
+///     let service_result = greeting.greeting("John".to_string());
+/// }
+///```
+///
+/// Calls made through generated methods could be recorded and then replayed, f.e. to reproduce
+/// locally a failure captured on CI:
+///```rust
+/// #[marine_test(config_path = "/path/to/Config.toml", modules_dir = "path/to/service/modules")]
+/// fn test() {
+///     let _recorder = marine_rs_sdk_test::CallRecorder::to_file("calls.json");
+///     let service_result = greeting.greeting("John".to_string());
+/// }
+///
+/// #[marine_test(config_path = "/path/to/Config.toml", modules_dir = "path/to/service/modules")]
+/// fn replay() {
+///     let recorded = marine_rs_sdk_test::CallLog::load("calls.json").unwrap();
+///     let replayed = recorded.replay(&marine);
+/// }
+///```
+///
+/// Records with the same name and definition in several modules of a service are generated once,
+/// so values could be passed between modules as is. Same-named records of the same shape get `From`
+/// conversions between each other, f.e. if they refer to other records that differ. Same-named
+/// records that can't be converted are deprecated, so the compiler describes their difference
+/// where they are used:
+///```rust
+/// #[marine_test(config_path = "/path/to/Config.toml", modules_dir = "path/to/service/modules")]
+/// fn test() {
+///     let user = users.get_user("John".to_string());
+///     let user: profiles_structs::User = user.into();
+///     profiles.store(user);
+/// }
+///```
+///
+/// Generated records derive `PartialEq` and `Default`, and also `Eq` with `Hash` if they have
+/// no float fields, so they could be compared by `assert_eq!` directly. Other derives could be
+/// requested by the `derives` attribute:
+///```rust
+/// #[marine_test(config_path = "/path/to/Config.toml", derives(PartialOrd, Ord))]
+/// fn test() {
+///     let user = users.get_user("John".to_string());
+///     assert_eq!(user, users_structs::User { name: "John".to_string(), ..Default::default() });
+/// }
+///```
+///
+/// With the `property_testing` feature generated records implement `proptest::arbitrary::Arbitrary`,
+/// so module functions could be called with random inputs by `marine_rs_sdk_test::marine_proptest`.
+///
+/// Applied to a module, it generates module definitions once for all tests inside,
+/// while each function marked with `#[test]` gets freshly instantiated services:
+///```rust
+/// #[marine_test(config_path = "/path/to/Config.toml", modules_dir = "path/to/service/modules")]
+/// mod tests {
+///     #[test]
+///     fn test() {
+///         let service_result = greeting.greeting("John".to_string());
+///         assert_eq!(&service_result, "Hi, John!");
+///     }
+/// }
+///```
 #[proc_macro_error]
 #[proc_macro_attribute]
 pub fn marine_test(attrs: TokenStream, input: TokenStream) -> TokenStream {
@@ -0,0 +1,43 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use marine_macro_impl::SectionDecodeError;
+use thiserror::Error as ThisError;
+
+#[derive(Debug, ThisError)]
+pub enum ModuleInterfaceError {
+    #[error("Wasm module can't be parsed: {0}")]
+    CorruptedWasmModule(anyhow::Error),
+
+    #[error("section {section_name} can't be decoded: {error}")]
+    CorruptedSection {
+        section_name: String,
+        #[source]
+        error: SectionDecodeError,
+    },
+
+    #[error("record {0} is used by the module interface, but isn't found in the module")]
+    UnknownRecord(String),
+
+    #[error("module contains several different records with name {0}")]
+    ConflictingRecords(String),
+
+    #[error("record {0} doesn't have named fields, only such records are supported")]
+    UnsupportedRecordFields(String),
+
+    #[error("function {0} has several outputs, only one is supported")]
+    ManyFnOutputs(String),
+}
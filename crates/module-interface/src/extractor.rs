@@ -0,0 +1,67 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::MIResult;
+use crate::ModuleInterfaceError;
+
+use marine_macro_impl::SDKAst;
+use marine_macro_impl::GENERATED_SECTION_PREFIX;
+use marine_macro_impl::GENERATED_SECTION_PREFIX_FCE;
+
+use std::path::Path;
+
+/// Collects interface items from all sections generated by the `#[marine]` macro,
+/// sections embedded by older sdk versions are also supported.
+pub fn extract_sdk_ast(wasm_path: impl AsRef<Path>) -> MIResult<Vec<SDKAst>> {
+    let module = walrus::ModuleConfig::new()
+        .parse_file(wasm_path)
+        .map_err(ModuleInterfaceError::CorruptedWasmModule)?;
+
+    collect_items(&module)
+}
+
+/// Does the same as `extract_sdk_ast`, but for a module already loaded into memory.
+pub fn extract_sdk_ast_from_bytes(wasm: &[u8]) -> MIResult<Vec<SDKAst>> {
+    let module = walrus::ModuleConfig::new()
+        .parse(wasm)
+        .map_err(ModuleInterfaceError::CorruptedWasmModule)?;
+
+    collect_items(&module)
+}
+
+fn collect_items(module: &walrus::Module) -> MIResult<Vec<SDKAst>> {
+    let mut items = Vec::new();
+
+    for (_, section) in module.customs.iter() {
+        let section_name = section.name();
+        if !section_name.starts_with(GENERATED_SECTION_PREFIX)
+            && !section_name.starts_with(GENERATED_SECTION_PREFIX_FCE)
+        {
+            continue;
+        }
+
+        let data = section.data(&<_>::default());
+        let section_items = marine_macro_impl::decode_section(&data).map_err(|error| {
+            ModuleInterfaceError::CorruptedSection {
+                section_name: section_name.to_string(),
+                error,
+            }
+        })?;
+        items.extend(section_items);
+    }
+
+    Ok(items)
+}
@@ -0,0 +1,116 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::MIResult;
+use crate::ModuleInterfaceError;
+
+use marine_macro_impl::FnSignature;
+use marine_macro_impl::ParsedType;
+use marine_macro_impl::RecordField;
+use marine_macro_impl::RecordFields;
+use marine_macro_impl::RecordType;
+use marine_macro_impl::SDKAst;
+
+use std::collections::HashMap;
+
+/// Records of a module by their Rust names, types in signatures refer to records by these names.
+pub type Records<'i> = HashMap<&'i str, &'i RecordType>;
+
+/// Collects all records of a module, fails if different records have the same Rust name
+/// or the same name in the module interface.
+pub fn collect_records(items: &[SDKAst]) -> MIResult<Records<'_>> {
+    let mut records = Records::new();
+    let mut rust_names = HashMap::new();
+
+    for item in items {
+        let record = match item {
+            SDKAst::Record(record) => record,
+            _ => continue,
+        };
+
+        // the same record could be embedded several times, f.e. if sections were merged
+        match records.insert(record.name.as_str(), record) {
+            Some(previous) if previous != record => {
+                return Err(ModuleInterfaceError::ConflictingRecords(
+                    record.name.clone(),
+                ))
+            }
+            _ => {}
+        }
+
+        let name = record_name(record);
+        match rust_names.insert(name, record.name.as_str()) {
+            Some(previous) if previous != record.name => {
+                return Err(ModuleInterfaceError::ConflictingRecords(name.to_string()))
+            }
+            _ => {}
+        }
+    }
+
+    Ok(records)
+}
+
+pub fn find_record<'i>(name: &str, records: &Records<'i>) -> MIResult<&'i RecordType> {
+    records
+        .get(name)
+        .copied()
+        .ok_or_else(|| ModuleInterfaceError::UnknownRecord(name.to_string()))
+}
+
+/// Returns a name of a record in the module interface.
+pub fn record_name(record: &RecordType) -> &str {
+    record.export_name.as_ref().unwrap_or(&record.name)
+}
+
+/// Returns fields of a record, records with unnamed fields can't be expressed by tools.
+pub fn named_fields(record: &RecordType) -> MIResult<&[RecordField]> {
+    match &record.fields {
+        RecordFields::Named(fields) => Ok(fields),
+        _ => Err(ModuleInterfaceError::UnsupportedRecordFields(
+            record.name.clone(),
+        )),
+    }
+}
+
+/// Returns the only output type of a function, functions with several outputs aren't supported.
+pub fn output_type(signature: &FnSignature) -> MIResult<Option<&ParsedType>> {
+    match signature.output_types.as_slice() {
+        [] => Ok(None),
+        [ty] => Ok(Some(ty)),
+        _ => Err(ModuleInterfaceError::ManyFnOutputs(signature.name.clone())),
+    }
+}
+
+/// Returns a name of a type that isn't a vector or a record, like it's named in Aqua.
+pub fn primitive_type_name(ty: &ParsedType) -> Option<&'static str> {
+    let name = match ty {
+        ParsedType::Boolean(_) => "bool",
+        ParsedType::U8(_) => "u8",
+        ParsedType::U16(_) => "u16",
+        ParsedType::U32(_) => "u32",
+        ParsedType::U64(_) => "u64",
+        ParsedType::I8(_) => "i8",
+        ParsedType::I16(_) => "i16",
+        ParsedType::I32(_) => "i32",
+        ParsedType::I64(_) => "i64",
+        ParsedType::F32(_) => "f32",
+        ParsedType::F64(_) => "f64",
+        ParsedType::Utf8Str(_) | ParsedType::Utf8String(_) => "string",
+        ParsedType::Vector(..) | ParsedType::Record(..) => return None,
+    };
+
+    Some(name)
+}
@@ -0,0 +1,52 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+#![doc(html_root_url = "https://docs.rs/marine-module-interface/0.1.0")]
+#![deny(
+    dead_code,
+    nonstandard_style,
+    unused_imports,
+    unused_mut,
+    unused_variables,
+    unused_unsafe,
+    unreachable_patterns
+)]
+#![warn(rust_2018_idioms)]
+
+//! Extracts interfaces that the `#[marine]` macro embeds into custom sections of a compiled
+//! module and provides helpers shared by tools generating definitions from them:
+//!```ignore
+//! let items = marine_module_interface::extract_sdk_ast("greeting.wasm")?;
+//! let records = marine_module_interface::collect_records(&items)?;
+//!```
+
+mod errors;
+mod extractor;
+mod interface;
+
+pub use errors::ModuleInterfaceError;
+pub use extractor::extract_sdk_ast;
+pub use extractor::extract_sdk_ast_from_bytes;
+pub use interface::collect_records;
+pub use interface::find_record;
+pub use interface::named_fields;
+pub use interface::output_type;
+pub use interface::primitive_type_name;
+pub use interface::record_name;
+pub use interface::Records;
+
+pub use marine_macro_impl::SDKAst;
+
+pub(crate) type MIResult<T> = std::result::Result<T, ModuleInterfaceError>;
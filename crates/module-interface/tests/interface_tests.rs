@@ -0,0 +1,39 @@
+use marine_macro_impl::SDKAst;
+use marine_module_interface::collect_records;
+use marine_module_interface::ModuleInterfaceError;
+
+const USER: &str = r#"{"ast_type":"Record","name":"User","fields":{"Named":[{"name":"id","ty":{"U64":"ByValue"}}]}}"#;
+const RENAMED_PERSON: &str = r#"{"ast_type":"Record","name":"Person","export_name":"User","fields":{"Named":[{"name":"id","ty":{"U64":"ByValue"}}]}}"#;
+const OTHER_USER: &str = r#"{"ast_type":"Record","name":"User","fields":{"Named":[{"name":"name","ty":{"Utf8String":"ByValue"}}]}}"#;
+
+fn items(items: &[&str]) -> Vec<SDKAst> {
+    items
+        .iter()
+        .map(|item| serde_json::from_str(item).expect("item should be valid"))
+        .collect()
+}
+
+#[test]
+fn same_record_embedded_twice_collected() {
+    let items = items(&[USER, USER]);
+    let records = collect_records(&items).expect("records should be collected");
+
+    assert_eq!(records.len(), 1);
+    assert!(records.contains_key("User"));
+}
+
+#[test]
+fn conflicting_records_rejected() {
+    match collect_records(&items(&[USER, OTHER_USER])) {
+        Err(ModuleInterfaceError::ConflictingRecords(name)) => assert_eq!(name, "User"),
+        result => panic!("expected ConflictingRecords, got {:?}", result),
+    }
+}
+
+#[test]
+fn records_with_same_interface_name_rejected() {
+    match collect_records(&items(&[USER, RENAMED_PERSON])) {
+        Err(ModuleInterfaceError::ConflictingRecords(name)) => assert_eq!(name, "User"),
+        result => panic!("expected ConflictingRecords, got {:?}", result),
+    }
+}
@@ -0,0 +1,96 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::SResult;
+
+use marine_macro_impl::FnSignature;
+use marine_macro_impl::ParsedType;
+use marine_macro_impl::RecordType;
+use marine_macro_impl::SDKAst;
+use marine_module_interface::collect_records;
+use marine_module_interface::find_record;
+use marine_module_interface::named_fields;
+use marine_module_interface::record_name;
+use marine_module_interface::Records;
+
+/// Exported functions of a module and records they use, both are sorted by names,
+/// so generated definitions don't depend on the order of items in a module.
+pub(crate) struct Interface<'i> {
+    pub(crate) functions: Vec<&'i FnSignature>,
+    pub(crate) records: Vec<&'i RecordType>,
+    all_records: Records<'i>,
+}
+
+impl<'i> Interface<'i> {
+    pub(crate) fn new(items: &'i [SDKAst]) -> SResult<Self> {
+        let mut functions = items
+            .iter()
+            .filter_map(|item| match item {
+                SDKAst::Function(function) => Some(&function.signature),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        functions.sort_by(|lhs, rhs| lhs.name.cmp(&rhs.name));
+
+        let mut interface = Self {
+            functions,
+            records: Vec::new(),
+            all_records: collect_records(items)?,
+        };
+        interface.collect_used_records()?;
+
+        Ok(interface)
+    }
+
+    pub(crate) fn record(&self, name: &str) -> SResult<&'i RecordType> {
+        Ok(find_record(name, &self.all_records)?)
+    }
+    fn collect_used_records(&mut self) -> SResult<()> {
+        let mut records = Records::new();
+        let types = self.functions.iter().flat_map(|signature| {
+            let arg_types = signature.arguments.iter().map(|argument| &argument.ty);
+            arg_types.chain(signature.output_types.iter())
+        });
+
+        for ty in types {
+            self.collect_records(ty, &mut records)?;
+        }
+
+        let mut records = records.values().copied().collect::<Vec<_>>();
+        records.sort_by(|lhs, rhs| record_name(lhs).cmp(record_name(rhs)));
+        self.records = records;
+
+        Ok(())
+    }
+
+    fn collect_records(&self, ty: &ParsedType, records: &mut Records<'i>) -> SResult<()> {
+        match ty {
+            ParsedType::Vector(ty, _) => self.collect_records(ty, records),
+            ParsedType::Record(name, _) => {
+                let record = self.record(name)?;
+                if records.insert(record.name.as_str(), record).is_some() {
+                    return Ok(());
+                }
+
+                for field in named_fields(record)? {
+                    self.collect_records(&field.ty, records)?;
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,177 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::interface::Interface;
+use crate::SResult;
+
+use marine_macro_impl::FnSignature;
+use marine_macro_impl::ParsedType;
+use marine_macro_impl::RecordType;
+use marine_macro_impl::SDKAst;
+use marine_module_interface::named_fields;
+use marine_module_interface::output_type;
+use marine_module_interface::record_name;
+use serde_json::json;
+use serde_json::Map;
+use serde_json::Value;
+
+const SCHEMA_DIALECT: &str = "http://json-schema.org/draft-07/schema#";
+
+/// Generates a JSON Schema document, where records used by exported functions are placed
+/// into `definitions` and functions are described in `functions`:
+///```json
+/// {
+///   "$schema": "http://json-schema.org/draft-07/schema#",
+///   "title": "greeting",
+///   "definitions": { "User": { "type": "object", ... } },
+///   "functions": {
+///     "get_user": {
+///       "arguments": { "type": "array", "items": [{ "title": "name", "type": "string" }], ... },
+///       "output": { "$ref": "#/definitions/User" }
+///     }
+///   }
+/// }
+///```
+/// Arguments are described by a tuple, because they are passed to a module by position.
+pub fn generate_json_schema(items: &[SDKAst], title: &str) -> SResult<Value> {
+    let interface = Interface::new(items)?;
+
+    let mut definitions = Map::new();
+    for record in interface.records.iter() {
+        let schema = record_schema(record, &interface)?;
+        definitions.insert(record_name(record).to_string(), schema);
+    }
+
+    let mut functions = Map::new();
+    for signature in interface.functions.iter() {
+        let schema = function_schema(signature, &interface)?;
+        functions.insert(signature.name.clone(), schema);
+    }
+
+    let schema = json!({
+        "$schema": SCHEMA_DIALECT,
+        "title": title,
+        "definitions": definitions,
+        "functions": functions,
+    });
+
+    Ok(schema)
+}
+
+fn record_schema(record: &RecordType, interface: &Interface<'_>) -> SResult<Value> {
+    let fields = named_fields(record)?;
+
+    let mut properties = Map::new();
+    let mut required = Vec::with_capacity(fields.len());
+    for field in fields {
+        // only named fields are possible here
+        let field_name = field.name.clone().unwrap_or_default();
+        let mut schema = type_schema(&field.ty, interface)?;
+        add_description(&mut schema, &field.docs);
+
+        properties.insert(field_name.clone(), schema);
+        required.push(Value::String(field_name));
+    }
+
+    let mut schema = json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    });
+    add_description(&mut schema, &record.docs);
+
+    Ok(schema)
+}
+
+fn function_schema(signature: &FnSignature, interface: &Interface<'_>) -> SResult<Value> {
+    let arguments = signature
+        .arguments
+        .iter()
+        .map(|argument| {
+            let mut schema = type_schema(&argument.ty, interface)?;
+            add_description(&mut schema, &argument.docs);
+            add_field(&mut schema, "title", Value::String(argument.name.clone()));
+            Ok(schema)
+        })
+        .collect::<SResult<Vec<_>>>()?;
+
+    let output = match output_type(signature)? {
+        Some(ty) => type_schema(ty, interface)?,
+        None => json!({ "type": "null" }),
+    };
+
+    let arguments_count = arguments.len();
+    let mut schema = json!({
+        "arguments": {
+            "type": "array",
+            "items": arguments,
+            "minItems": arguments_count,
+            "maxItems": arguments_count,
+        },
+        "output": output,
+    });
+    add_description(&mut schema, &signature.docs);
+
+    Ok(schema)
+}
+
+fn type_schema(ty: &ParsedType, interface: &Interface<'_>) -> SResult<Value> {
+    let schema = match ty {
+        ParsedType::Boolean(_) => json!({ "type": "boolean" }),
+        ParsedType::U8(_) => integer_schema(u8::MIN, u8::MAX),
+        ParsedType::U16(_) => integer_schema(u16::MIN, u16::MAX),
+        ParsedType::U32(_) => integer_schema(u32::MIN, u32::MAX),
+        ParsedType::U64(_) => integer_schema(u64::MIN, u64::MAX),
+        ParsedType::I8(_) => integer_schema(i8::MIN, i8::MAX),
+        ParsedType::I16(_) => integer_schema(i16::MIN, i16::MAX),
+        ParsedType::I32(_) => integer_schema(i32::MIN, i32::MAX),
+        ParsedType::I64(_) => integer_schema(i64::MIN, i64::MAX),
+        ParsedType::F32(_) | ParsedType::F64(_) => json!({ "type": "number" }),
+        ParsedType::Utf8Str(_) | ParsedType::Utf8String(_) => json!({ "type": "string" }),
+        ParsedType::Vector(ty, _) => json!({
+            "type": "array",
+            "items": type_schema(ty, interface)?,
+        }),
+        ParsedType::Record(name, _) => {
+            let record = interface.record(name)?;
+            json!({ "$ref": format!("#/definitions/{}", record_name(record)) })
+        }
+    };
+
+    Ok(schema)
+}
+
+fn integer_schema<T: Into<Value>>(minimum: T, maximum: T) -> Value {
+    json!({
+        "type": "integer",
+        "minimum": minimum.into(),
+        "maximum": maximum.into(),
+    })
+}
+
+fn add_description(schema: &mut Value, docs: &Option<String>) {
+    if let Some(docs) = docs {
+        add_field(schema, "description", Value::String(docs.clone()));
+    }
+}
+
+fn add_field(schema: &mut Value, name: &str, value: Value) {
+    // all schemas are created by json! from objects
+    if let Value::Object(schema) = schema {
+        schema.insert(name.to_string(), value);
+    }
+}
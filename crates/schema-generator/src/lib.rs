@@ -0,0 +1,48 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#![doc(html_root_url = "https://docs.rs/marine-schema-generator/0.1.0")]
+#![deny(
+    dead_code,
+    nonstandard_style,
+    unused_imports,
+    unused_mut,
+    unused_variables,
+    unused_unsafe,
+    unreachable_patterns
+)]
+#![warn(rust_2018_idioms)]
+
+//! Generates JSON Schema documents and TypeScript declarations of records and functions
+//! from interfaces that the `#[marine]` macro embeds into custom sections of a module.
+//!
+//! Types are mapped to JSON in the same way as Marine converts values to JSON:
+//! - integers are JSON integers bounded by the range of a type, 64-bit integers are also
+//!   JSON numbers, so values outside of `Number.MAX_SAFE_INTEGER` lose precision in JavaScript,
+//! - floats are JSON numbers,
+//! - `Vec<u8>` is an array of integers in `0..=255`, like other vectors,
+//! - records are JSON objects with all fields required.
+
+mod errors;
+mod interface;
+mod json_schema_generator;
+mod typescript_generator;
+
+pub use errors::SchemaGeneratorError;
+pub use json_schema_generator::generate_json_schema;
+pub use typescript_generator::generate_typescript;
+
+pub(crate) type SResult<T> = std::result::Result<T, SchemaGeneratorError>;
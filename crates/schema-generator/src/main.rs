@@ -0,0 +1,149 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use marine_schema_generator::generate_json_schema;
+use marine_schema_generator::generate_typescript;
+use marine_module_interface::extract_sdk_ast;
+
+use std::path::PathBuf;
+
+const USAGE: &str = "\
+Generates JSON Schema or TypeScript definitions of a Marine module
+
+USAGE:
+    marine-schema-generator <module.wasm> --format <json-schema|typescript> [--name <name>] [--output <file>]
+
+OPTIONS:
+    --format <format>    json-schema or typescript
+    --name <name>        schema title or name of the TypeScript interface with functions,
+                         it's derived from the module file name by default
+    --output <file>      file to write definitions to, stdout is used by default";
+
+enum Format {
+    JsonSchema,
+    TypeScript,
+}
+
+struct Args {
+    wasm_path: PathBuf,
+    format: Format,
+    name: Option<String>,
+    output_path: Option<PathBuf>,
+}
+
+fn main() {
+    if std::env::args().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{}", USAGE);
+        return;
+    }
+
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    if let Err(message) = run(args) {
+        eprintln!("error: {}", message);
+        std::process::exit(1);
+    }
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let module_name = args
+        .wasm_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .ok_or_else(|| format!("{} isn't a path to a file", args.wasm_path.display()))?;
+
+    let items = extract_sdk_ast(&args.wasm_path).map_err(|e| e.to_string())?;
+    let definitions = match args.format {
+        Format::JsonSchema => {
+            let title = args.name.unwrap_or(module_name);
+            let schema = generate_json_schema(&items, &title).map_err(|e| e.to_string())?;
+            // Value is always serializable
+            let mut schema = serde_json::to_string_pretty(&schema).unwrap();
+            schema.push('\n');
+            schema
+        }
+        Format::TypeScript => {
+            let name = args.name.unwrap_or_else(|| upper_camel_case(&module_name));
+            generate_typescript(&items, &name).map_err(|e| e.to_string())?
+        }
+    };
+
+    match args.output_path {
+        Some(output_path) => std::fs::write(&output_path, definitions)
+            .map_err(|e| format!("can't write {}: {}", output_path.display(), e)),
+        None => {
+            print!("{}", definitions);
+            Ok(())
+        }
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut wasm_path = None;
+    let mut format = None;
+    let mut name = None;
+    let mut output_path = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                format = match option_value(&arg, args.next())?.as_str() {
+                    "json-schema" => Some(Format::JsonSchema),
+                    "typescript" => Some(Format::TypeScript),
+                    unknown => return Err(format!("unknown format {}", unknown)),
+                }
+            }
+            "--name" => name = Some(option_value(&arg, args.next())?),
+            "--output" => output_path = Some(PathBuf::from(option_value(&arg, args.next())?)),
+            _ if arg.starts_with('-') => return Err(format!("unknown option {}", arg)),
+            _ if wasm_path.is_none() => wasm_path = Some(PathBuf::from(arg)),
+            _ => return Err(format!("unexpected argument {}", arg)),
+        }
+    }
+
+    let wasm_path = wasm_path.ok_or_else(|| String::from("path to a module isn't specified"))?;
+    let format = format.ok_or_else(|| String::from("format isn't specified"))?;
+
+    Ok(Args {
+        wasm_path,
+        format,
+        name,
+        output_path,
+    })
+}
+
+fn option_value(option: &str, value: Option<String>) -> Result<String, String> {
+    value.ok_or_else(|| format!("{} requires a value", option))
+}
+
+/// Converts a module name like `user-store` to a type name like `UserStore`.
+fn upper_camel_case(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            // parts are non-empty, so the first char is always present
+            let first = chars.next().unwrap_or_default().to_ascii_uppercase();
+            std::iter::once(first).chain(chars).collect::<String>()
+        })
+        .collect()
+}
@@ -0,0 +1,228 @@
+/*
+ * Copyright 2020 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::interface::Interface;
+use crate::SResult;
+use crate::SchemaGeneratorError;
+
+use marine_macro_impl::FnSignature;
+use marine_macro_impl::ParsedType;
+use marine_macro_impl::RecordType;
+use marine_macro_impl::SDKAst;
+use marine_module_interface::named_fields;
+use marine_module_interface::output_type;
+use marine_module_interface::record_name;
+
+use std::fmt::Write;
+
+const INDENT: &str = "  ";
+
+/// Words that can't be used as parameter names in TypeScript, but are valid Rust identifiers.
+const RESERVED_WORDS: &[&str] = &[
+    "case",
+    "catch",
+    "class",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "export",
+    "extends",
+    "finally",
+    "function",
+    "import",
+    "instanceof",
+    "new",
+    "null",
+    "switch",
+    "this",
+    "throw",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "with",
+];
+
+/// Generates an interface for each record used by exported functions and an interface with
+/// the given name for functions:
+///```typescript
+/// export interface User {
+///   name: string;
+/// }
+///
+/// export interface Greeting {
+///   get_user(name: string): User;
+/// }
+///```
+pub fn generate_typescript(items: &[SDKAst], interface_name: &str) -> SResult<String> {
+    let interface = Interface::new(items)?;
+    check_type_name(interface_name)?;
+
+    let mut typescript = String::new();
+    for record in interface.records.iter() {
+        write_record(&mut typescript, record, &interface)?;
+        typescript.push('\n');
+    }
+
+    writeln!(typescript, "export interface {} {{", interface_name).unwrap();
+    for signature in interface.functions.iter() {
+        write_function(&mut typescript, signature, &interface)?;
+    }
+    typescript.push_str("}\n");
+
+    Ok(typescript)
+}
+
+fn write_record(
+    typescript: &mut String,
+    record: &RecordType,
+    interface: &Interface<'_>,
+) -> SResult<()> {
+    let name = record_name(record);
+    check_type_name(name)?;
+
+    write_docs(typescript, &record.docs, "");
+    writeln!(typescript, "export interface {} {{", name).unwrap();
+    for field in named_fields(record)? {
+        // only named fields are possible here
+        let field_name = field.name.as_deref().unwrap_or_default();
+        let ty = typescript_type(&field.ty, interface)?;
+
+        write_docs(typescript, &field.docs, INDENT);
+        writeln!(
+            typescript,
+            "{}{}: {};",
+            INDENT,
+            property_name(field_name),
+            ty
+        )
+        .unwrap();
+    }
+    typescript.push_str("}\n");
+
+    Ok(())
+}
+
+fn write_function(
+    typescript: &mut String,
+    signature: &FnSignature,
+    interface: &Interface<'_>,
+) -> SResult<()> {
+    let arguments = signature
+        .arguments
+        .iter()
+        .map(|argument| {
+            let ty = typescript_type(&argument.ty, interface)?;
+            Ok(format!("{}: {}", parameter_name(&argument.name), ty))
+        })
+        .collect::<SResult<Vec<_>>>()?;
+
+    let output = match output_type(signature)? {
+        Some(ty) => typescript_type(ty, interface)?,
+        None => "void".to_string(),
+    };
+
+    write_docs(typescript, &signature.docs, INDENT);
+    writeln!(
+        typescript,
+        "{}{}({}): {};",
+        INDENT,
+        property_name(&signature.name),
+        arguments.join(", "),
+        output
+    )
+    .unwrap();
+
+    Ok(())
+}
+
+fn typescript_type(ty: &ParsedType, interface: &Interface<'_>) -> SResult<String> {
+    let ty = match ty {
+        ParsedType::Boolean(_) => "boolean".to_string(),
+        // 64-bit integers are JSON numbers too, see the crate docs
+        ParsedType::U8(_)
+        | ParsedType::U16(_)
+        | ParsedType::U32(_)
+        | ParsedType::U64(_)
+        | ParsedType::I8(_)
+        | ParsedType::I16(_)
+        | ParsedType::I32(_)
+        | ParsedType::I64(_)
+        | ParsedType::F32(_)
+        | ParsedType::F64(_) => "number".to_string(),
+        ParsedType::Utf8Str(_) | ParsedType::Utf8String(_) => "string".to_string(),
+        ParsedType::Vector(ty, _) => format!("{}[]", typescript_type(ty, interface)?),
+        ParsedType::Record(name, _) => record_name(interface.record(name)?).to_string(),
+    };
+
+    Ok(ty)
+}
+
+fn write_docs(typescript: &mut String, docs: &Option<String>, indent: &str) {
+    let docs = match docs {
+        Some(docs) => docs,
+        None => return,
+    };
+
+    if !docs.contains('\n') {
+        writeln!(typescript, "{}/** {} */", indent, docs).unwrap();
+        return;
+    }
+
+    writeln!(typescript, "{}/**", indent).unwrap();
+    for line in docs.split('\n') {
+        if line.is_empty() {
+            writeln!(typescript, "{} *", indent).unwrap();
+        } else {
+            writeln!(typescript, "{} * {}", indent, line).unwrap();
+        }
+    }
+    writeln!(typescript, "{} */", indent).unwrap();
+}
+
+/// Quotes names of properties and methods that aren't identifiers, f.e. renamed by
+/// `#[marine(rename = "user-id")]`.
+fn property_name(name: &str) -> String {
+    if is_identifier(name) {
+        name.to_string()
+    } else {
+        format!("{:?}", name)
+    }
+}
+
+/// Parameter names can't be quoted, but they don't affect calls, so reserved words are renamed.
+fn parameter_name(name: &str) -> String {
+    if RESERVED_WORDS.contains(&name) {
+        format!("{}_", name)
+    } else {
+        name.to_string()
+    }
+}
+
+fn check_type_name(name: &str) -> SResult<()> {
+    if is_identifier(name) && !RESERVED_WORDS.contains(&name) {
+        Ok(())
+    } else {
+        Err(SchemaGeneratorError::InvalidTypeName(name.to_string()))
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
@@ -0,0 +1,131 @@
+use marine_macro_impl::SDKAst;
+use marine_module_interface::ModuleInterfaceError;
+use marine_schema_generator::generate_json_schema;
+use marine_schema_generator::generate_typescript;
+use marine_schema_generator::SchemaGeneratorError;
+
+use serde_json::json;
+
+const GET_USER: &str = r#"{"ast_type":"Function","signature":{"name":"get_user","arguments":[{"name":"id","ty":{"I64":"ByValue"},"docs":"id of the user"}],"output_types":[{"Record":["User","ByValue"]}],"docs":"Returns a user.\n\nUser is searched by id."}}"#;
+const SET_AVATAR: &str = r#"{"ast_type":"Function","signature":{"name":"set-avatar","arguments":[{"name":"default","ty":{"Vector":[{"U8":"ByValue"},"ByRef"]}}],"output_types":[]}}"#;
+const USER: &str = r#"{"ast_type":"Record","name":"User","docs":"A user.","fields":{"Named":[{"name":"user-id","ty":{"U64":"ByValue"},"docs":"Unique id."},{"name":"rating","ty":{"F64":"ByValue"}},{"name":"tags","ty":{"Vector":[{"Utf8String":"ByValue"},"ByValue"]}}]}}"#;
+const UNUSED: &str = r#"{"ast_type":"Record","name":"Unused","fields":{"Named":[{"name":"flag","ty":{"Boolean":"ByValue"}}]}}"#;
+
+fn items(items: &[&str]) -> Vec<SDKAst> {
+    items
+        .iter()
+        .map(|item| serde_json::from_str(item).expect("item should be valid"))
+        .collect()
+}
+
+#[test]
+fn json_schema_generated() {
+    let schema = generate_json_schema(&items(&[USER, SET_AVATAR, UNUSED, GET_USER]), "users")
+        .expect("schema should be generated");
+
+    let expected = json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "users",
+        "definitions": {
+            "User": {
+                "type": "object",
+                "description": "A user.",
+                "properties": {
+                    "user-id": {
+                        "type": "integer",
+                        "minimum": 0,
+                        "maximum": 18446744073709551615u64,
+                        "description": "Unique id.",
+                    },
+                    "rating": { "type": "number" },
+                    "tags": { "type": "array", "items": { "type": "string" } },
+                },
+                "required": ["user-id", "rating", "tags"],
+                "additionalProperties": false,
+            },
+        },
+        "functions": {
+            "get_user": {
+                "description": "Returns a user.\n\nUser is searched by id.",
+                "arguments": {
+                    "type": "array",
+                    "items": [{
+                        "title": "id",
+                        "description": "id of the user",
+                        "type": "integer",
+                        "minimum": -9223372036854775808i64,
+                        "maximum": 9223372036854775807i64,
+                    }],
+                    "minItems": 1,
+                    "maxItems": 1,
+                },
+                "output": { "$ref": "#/definitions/User" },
+            },
+            "set-avatar": {
+                "arguments": {
+                    "type": "array",
+                    "items": [{
+                        "title": "default",
+                        "type": "array",
+                        "items": { "type": "integer", "minimum": 0, "maximum": 255 },
+                    }],
+                    "minItems": 1,
+                    "maxItems": 1,
+                },
+                "output": { "type": "null" },
+            },
+        },
+    });
+
+    assert_eq!(schema, expected);
+}
+
+#[test]
+fn typescript_generated() {
+    let typescript = generate_typescript(&items(&[USER, SET_AVATAR, UNUSED, GET_USER]), "Users")
+        .expect("typescript should be generated");
+
+    let expected = r#"/** A user. */
+export interface User {
+  /** Unique id. */
+  "user-id": number;
+  rating: number;
+  tags: string[];
+}
+
+export interface Users {
+  /**
+   * Returns a user.
+   *
+   * User is searched by id.
+   */
+  get_user(id: number): User;
+  "set-avatar"(default_: number[]): void;
+}
+"#;
+
+    assert_eq!(typescript, expected);
+}
+
+#[test]
+fn unknown_record_rejected() {
+    match generate_typescript(&items(&[GET_USER]), "Users") {
+        Err(SchemaGeneratorError::InterfaceError(ModuleInterfaceError::UnknownRecord(name))) => {
+            assert_eq!(name, "User")
+        }
+        result => panic!("expected UnknownRecord, got {:?}", result),
+    }
+}
+
+#[test]
+fn invalid_type_name_rejected() {
+    let renamed_user = USER.replace(
+        r#""name":"User","#,
+        r#""name":"User","export_name":"user-v2","#,
+    );
+
+    match generate_typescript(&items(&[GET_USER, &renamed_user]), "Users") {
+        Err(SchemaGeneratorError::InvalidTypeName(name)) => assert_eq!(name, "user-v2"),
+        result => panic!("expected InvalidTypeName, got {:?}", result),
+    }
+}
@@ -0,0 +1,256 @@
+/*
+ * Copyright 2021 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::CallParametersError;
+
+use fluence_app_service::CallParameters;
+use fluence_app_service::SecurityTetraplet;
+
+/// Builds call parameters for a function with known argument names, it's created by
+/// `<function_name>_cp_builder` methods generated by `marine_test`:
+///```ignore
+/// let cp = greeting
+///     .greeting_cp_builder()
+///     .init_peer_id("init_peer_id")
+///     .argument_tetraplet("name", tetraplet)
+///     .build()
+///     .unwrap();
+///
+/// let result = greeting.greeting_cp("John".to_string(), cp);
+///```
+#[derive(Clone, Debug)]
+pub struct CallParametersBuilder {
+    call_parameters: CallParameters,
+    argument_names: &'static [&'static str],
+    // the first error is saved to be returned from build to keep the builder chain fluent
+    error: Option<CallParametersError>,
+}
+
+impl CallParametersBuilder {
+    /// Creates a builder for a function with the provided argument names,
+    /// tetraplets of all arguments are empty by default.
+    pub fn new(argument_names: &'static [&'static str]) -> Self {
+        let call_parameters = CallParameters {
+            tetraplets: vec![Vec::new(); argument_names.len()],
+            ..<_>::default()
+        };
+
+        Self {
+            call_parameters,
+            argument_names,
+            error: None,
+        }
+    }
+
+    pub fn init_peer_id(mut self, init_peer_id: impl Into<String>) -> Self {
+        self.call_parameters.init_peer_id = init_peer_id.into();
+        self
+    }
+
+    pub fn service_id(mut self, service_id: impl Into<String>) -> Self {
+        self.call_parameters.service_id = service_id.into();
+        self
+    }
+
+    pub fn service_creator_peer_id(mut self, service_creator_peer_id: impl Into<String>) -> Self {
+        self.call_parameters.service_creator_peer_id = service_creator_peer_id.into();
+        self
+    }
+
+    pub fn host_id(mut self, host_id: impl Into<String>) -> Self {
+        self.call_parameters.host_id = host_id.into();
+        self
+    }
+
+    pub fn particle_id(mut self, particle_id: impl Into<String>) -> Self {
+        self.call_parameters.particle_id = particle_id.into();
+        self
+    }
+
+    /// Adds a tetraplet to the argument with the provided name.
+    pub fn argument_tetraplet(
+        self,
+        argument_name: impl AsRef<str>,
+        tetraplet: SecurityTetraplet,
+    ) -> Self {
+        self.with_argument_tetraplets(argument_name.as_ref(), |tetraplets| {
+            tetraplets.push(tetraplet)
+        })
+    }
+
+    /// Sets all tetraplets of the argument with the provided name.
+    pub fn argument_tetraplets(
+        self,
+        argument_name: impl AsRef<str>,
+        tetraplets: Vec<SecurityTetraplet>,
+    ) -> Self {
+        self.with_argument_tetraplets(argument_name.as_ref(), |argument_tetraplets| {
+            *argument_tetraplets = tetraplets
+        })
+    }
+
+    /// Sets tetraplets of all arguments, their count must be equal to the argument count.
+    pub fn tetraplets(mut self, tetraplets: Vec<Vec<SecurityTetraplet>>) -> Self {
+        if tetraplets.len() != self.argument_names.len() {
+            let error = CallParametersError::TetrapletsCountMismatch {
+                expected: self.argument_names.len(),
+                actual: tetraplets.len(),
+            };
+            return self.with_error(error);
+        }
+
+        self.call_parameters.tetraplets = tetraplets;
+        self
+    }
+
+    /// Returns built call parameters or the first error occurred while building.
+    pub fn build(self) -> Result<CallParameters, CallParametersError> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.call_parameters),
+        }
+    }
+
+    fn with_argument_tetraplets(
+        mut self,
+        argument_name: &str,
+        updater: impl FnOnce(&mut Vec<SecurityTetraplet>),
+    ) -> Self {
+        let position = self
+            .argument_names
+            .iter()
+            .position(|name| *name == argument_name);
+
+        match position {
+            Some(position) => {
+                updater(&mut self.call_parameters.tetraplets[position]);
+                self
+            }
+            None => {
+                let error = CallParametersError::UnknownArgument {
+                    argument_name: argument_name.to_string(),
+                    argument_names: self.argument_names.join(", "),
+                };
+                self.with_error(error)
+            }
+        }
+    }
+
+    fn with_error(mut self, error: CallParametersError) -> Self {
+        self.error.get_or_insert(error);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CallParametersBuilder;
+    use crate::CallParametersError;
+
+    use fluence_app_service::SecurityTetraplet;
+
+    const ARGUMENT_NAMES: &[&str] = &["name", "age"];
+
+    fn tetraplet(peer_pk: &str) -> SecurityTetraplet {
+        SecurityTetraplet {
+            peer_pk: peer_pk.to_string(),
+            service_id: "service_id".to_string(),
+            function_name: "function_name".to_string(),
+            json_path: String::new(),
+        }
+    }
+
+    #[test]
+    fn successful_build() {
+        let call_parameters = CallParametersBuilder::new(ARGUMENT_NAMES)
+            .init_peer_id("init_peer_id")
+            .service_id("service_id")
+            .service_creator_peer_id("service_creator_peer_id")
+            .host_id("host_id")
+            .particle_id("particle_id")
+            .argument_tetraplet("name", tetraplet("peer_1"))
+            .argument_tetraplets("age", vec![tetraplet("peer_2"), tetraplet("peer_3")])
+            .build()
+            .unwrap();
+
+        assert_eq!(call_parameters.init_peer_id, "init_peer_id");
+        assert_eq!(call_parameters.service_id, "service_id");
+        assert_eq!(
+            call_parameters.service_creator_peer_id,
+            "service_creator_peer_id"
+        );
+        assert_eq!(call_parameters.host_id, "host_id");
+        assert_eq!(call_parameters.particle_id, "particle_id");
+        assert_eq!(
+            call_parameters.tetraplets,
+            vec![
+                vec![tetraplet("peer_1")],
+                vec![tetraplet("peer_2"), tetraplet("peer_3")]
+            ]
+        );
+    }
+
+    #[test]
+    fn tetraplets_are_empty_by_default() {
+        let call_parameters = CallParametersBuilder::new(ARGUMENT_NAMES).build().unwrap();
+
+        assert_eq!(call_parameters.tetraplets, vec![Vec::new(), Vec::new()]);
+    }
+
+    #[test]
+    fn unknown_argument() {
+        let error = CallParametersBuilder::new(ARGUMENT_NAMES)
+            .argument_tetraplet("surname", tetraplet("peer_1"))
+            .build()
+            .unwrap_err();
+
+        let expected_error = CallParametersError::UnknownArgument {
+            argument_name: "surname".to_string(),
+            argument_names: "name, age".to_string(),
+        };
+        assert_eq!(error, expected_error);
+    }
+
+    #[test]
+    fn tetraplets_count_mismatch() {
+        let error = CallParametersBuilder::new(ARGUMENT_NAMES)
+            .tetraplets(vec![vec![tetraplet("peer_1")]])
+            .build()
+            .unwrap_err();
+
+        let expected_error = CallParametersError::TetrapletsCountMismatch {
+            expected: 2,
+            actual: 1,
+        };
+        assert_eq!(error, expected_error);
+    }
+
+    #[test]
+    fn first_error_wins() {
+        let error = CallParametersBuilder::new(ARGUMENT_NAMES)
+            .argument_tetraplets("surname", Vec::new())
+            .tetraplets(Vec::new())
+            .argument_tetraplet("city", tetraplet("peer_1"))
+            .build()
+            .unwrap_err();
+
+        let expected_error = CallParametersError::UnknownArgument {
+            argument_name: "surname".to_string(),
+            argument_names: "name, age".to_string(),
+        };
+        assert_eq!(error, expected_error);
+    }
+}
@@ -0,0 +1,346 @@
+/*
+ * Copyright 2021 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Records calls made through methods generated by `marine_test` and replays them.
+//! Recording is enabled per test thread by creating a `CallRecorder`, so tests running
+//! in parallel don't mix their records. Recorders could be nested, each of them records
+//! all calls made while it's alive.
+
+use fluence_app_service::AppService;
+use fluence_app_service::AppServiceError;
+use fluence_app_service::CallParameters;
+
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+
+use std::cell::RefCell;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+
+thread_local!(static ACTIVE_RECORDERS: RefCell<Vec<ActiveRecorder>> = RefCell::new(Vec::new()));
+
+static NEXT_RECORDER_ID: AtomicU64 = AtomicU64::new(0);
+
+struct ActiveRecorder {
+    id: u64,
+    calls: Vec<CallRecord>,
+}
+
+/// Describes one call of a module function.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CallRecord {
+    pub module_name: String,
+    pub function_name: String,
+    /// Arguments in the form they were passed to Marine, i.e. a JSON array.
+    pub arguments: Value,
+    pub call_parameters: CallParameters,
+    /// Result returned by a function or a message of an error occurred during the call.
+    pub result: Result<Value, String>,
+    pub duration: Duration,
+}
+
+/// A sequence of recorded calls in order they were made.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CallLog {
+    pub calls: Vec<CallRecord>,
+}
+
+impl CallLog {
+    /// Loads a call log saved by `save` or by a recorder created with `CallRecorder::to_file`.
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let log = std::fs::read(path)?;
+        let log = serde_json::from_slice(&log)?;
+
+        Ok(log)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let log = serde_json::to_vec_pretty(self)?;
+        std::fs::write(path, log)
+    }
+
+    /// Calls recorded functions with recorded arguments and call parameters in the same order
+    /// and returns a log of these calls, so it could be compared with the recorded one.
+    /// If a recorder is active, replayed calls are recorded as well.
+    pub fn replay(&self, marine: &RefCell<AppService>) -> CallLog {
+        let calls = self
+            .calls
+            .iter()
+            .map(|call| {
+                let (result, duration) = timed_call(
+                    marine,
+                    &call.module_name,
+                    &call.function_name,
+                    call.arguments.clone(),
+                    call.call_parameters.clone(),
+                );
+
+                let record = CallRecord {
+                    result: result.map_err(|e| e.to_string()),
+                    duration,
+                    ..call.clone()
+                };
+                record_call(&record);
+
+                record
+            })
+            .collect();
+
+        CallLog { calls }
+    }
+}
+
+/// Records calls made by the current thread while it's alive, f.e.:
+///```ignore
+/// let recorder = marine_rs_sdk_test::CallRecorder::to_file("calls.json");
+/// greeting.greeting("John".to_string());
+/// // calls.json is written when the recorder is dropped, even if the test panicked
+///```
+/// A failed test could then be reproduced by `CallLog::load("calls.json")?.replay(&marine)`.
+pub struct CallRecorder {
+    id: u64,
+    log_path: Option<PathBuf>,
+}
+
+impl CallRecorder {
+    /// Starts recording calls to an in-memory buffer.
+    pub fn new() -> Self {
+        let id = NEXT_RECORDER_ID.fetch_add(1, Ordering::Relaxed);
+        ACTIVE_RECORDERS.with(|recorders| {
+            recorders.borrow_mut().push(ActiveRecorder {
+                id,
+                calls: Vec::new(),
+            })
+        });
+
+        Self { id, log_path: None }
+    }
+
+    /// Starts recording calls, the log is saved to the provided path when the recorder is dropped.
+    pub fn to_file(log_path: impl Into<PathBuf>) -> Self {
+        let mut recorder = Self::new();
+        recorder.log_path = Some(log_path.into());
+
+        recorder
+    }
+
+    /// Returns calls recorded so far.
+    pub fn calls(&self) -> CallLog {
+        let calls = ACTIVE_RECORDERS.with(|recorders| {
+            recorders
+                .borrow()
+                .iter()
+                .find(|recorder| recorder.id == self.id)
+                .map(|recorder| recorder.calls.clone())
+                .unwrap_or_default()
+        });
+
+        CallLog { calls }
+    }
+}
+
+impl Default for CallRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for CallRecorder {
+    fn drop(&mut self) {
+        // recorders are searched by id, because they could be dropped not in the creation order
+        let calls = ACTIVE_RECORDERS.with(|recorders| {
+            let mut recorders = recorders.borrow_mut();
+            match recorders.iter().position(|recorder| recorder.id == self.id) {
+                Some(position) => recorders.remove(position).calls,
+                None => Vec::new(),
+            }
+        });
+
+        if let Some(log_path) = &self.log_path {
+            // drop could be called while the thread is panicking, so errors are only reported
+            if let Err(e) = (CallLog { calls }).save(log_path) {
+                eprintln!("call log can't be saved to {:?}: {}", log_path, e);
+            }
+        }
+    }
+}
+
+/// Calls a module function and records the call if a recorder is active.
+/// Intended to be used only by methods generated by `marine_test`.
+pub fn call_module(
+    marine: &RefCell<AppService>,
+    module_name: &str,
+    function_name: &str,
+    arguments: Value,
+    call_parameters: CallParameters,
+) -> Result<Value, AppServiceError> {
+    let is_recording = ACTIVE_RECORDERS.with(|recorders| !recorders.borrow().is_empty());
+    if !is_recording {
+        return marine.borrow_mut().call_module(
+            module_name,
+            function_name,
+            arguments,
+            call_parameters,
+        );
+    }
+
+    let (result, duration) = timed_call(
+        marine,
+        module_name,
+        function_name,
+        arguments.clone(),
+        call_parameters.clone(),
+    );
+
+    let record = CallRecord {
+        module_name: module_name.to_string(),
+        function_name: function_name.to_string(),
+        arguments,
+        call_parameters,
+        result: result
+            .as_ref()
+            .map(Clone::clone)
+            .map_err(ToString::to_string),
+        duration,
+    };
+
+    record_call(&record);
+
+    result
+}
+
+fn record_call(record: &CallRecord) {
+    ACTIVE_RECORDERS.with(|recorders| {
+        for recorder in recorders.borrow_mut().iter_mut() {
+            recorder.calls.push(record.clone());
+        }
+    });
+}
+
+fn timed_call(
+    marine: &RefCell<AppService>,
+    module_name: &str,
+    function_name: &str,
+    arguments: Value,
+    call_parameters: CallParameters,
+) -> (Result<Value, AppServiceError>, Duration) {
+    let start = Instant::now();
+    let result =
+        marine
+            .borrow_mut()
+            .call_module(module_name, function_name, arguments, call_parameters);
+
+    (result, start.elapsed())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::record_call;
+    use super::CallLog;
+    use super::CallRecord;
+    use super::CallRecorder;
+
+    use fluence_app_service::CallParameters;
+    use serde_json::json;
+
+    use std::time::Duration;
+
+    fn call_record(function_name: &str) -> CallRecord {
+        CallRecord {
+            module_name: "greeting".to_string(),
+            function_name: function_name.to_string(),
+            arguments: json!(["John"]),
+            call_parameters: CallParameters {
+                init_peer_id: "init_peer_id".to_string(),
+                ..<_>::default()
+            },
+            result: Ok(json!("Hi, John")),
+            duration: Duration::from_millis(42),
+        }
+    }
+
+    fn temp_log_path() -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("call_log_{}.json", uuid::Uuid::new_v4()))
+    }
+
+    #[test]
+    fn save_load_round_trip() {
+        let failed_call = CallRecord {
+            result: Err("function trapped".to_string()),
+            ..call_record("fail")
+        };
+        let log = CallLog {
+            calls: vec![call_record("greeting"), failed_call],
+        };
+        let log_path = temp_log_path();
+
+        log.save(&log_path).unwrap();
+        let loaded_log = CallLog::load(&log_path).unwrap();
+        std::fs::remove_file(&log_path).unwrap();
+
+        assert_eq!(loaded_log, log);
+    }
+
+    #[test]
+    fn recorder_saves_log_on_drop() {
+        let log_path = temp_log_path();
+
+        let recorder = CallRecorder::to_file(&log_path);
+        record_call(&call_record("greeting"));
+        drop(recorder);
+
+        let loaded_log = CallLog::load(&log_path).unwrap();
+        std::fs::remove_file(&log_path).unwrap();
+
+        assert_eq!(loaded_log.calls, vec![call_record("greeting")]);
+    }
+
+    #[test]
+    fn nested_recorders() {
+        let outer_recorder = CallRecorder::new();
+        record_call(&call_record("outer_1"));
+
+        let inner_recorder = CallRecorder::new();
+        record_call(&call_record("inner"));
+        assert_eq!(inner_recorder.calls().calls, vec![call_record("inner")]);
+        drop(inner_recorder);
+
+        record_call(&call_record("outer_2"));
+
+        let expected_calls = vec![
+            call_record("outer_1"),
+            call_record("inner"),
+            call_record("outer_2"),
+        ];
+        assert_eq!(outer_recorder.calls().calls, expected_calls);
+    }
+
+    #[test]
+    fn recorders_dropped_out_of_order() {
+        let first_recorder = CallRecorder::new();
+        let second_recorder = CallRecorder::new();
+        drop(first_recorder);
+
+        record_call(&call_record("greeting"));
+
+        assert_eq!(second_recorder.calls().calls, vec![call_record("greeting")]);
+    }
+}
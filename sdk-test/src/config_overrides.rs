@@ -0,0 +1,95 @@
+/*
+ * Copyright 2021 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use fluence_app_service::TomlAppServiceConfig;
+use fluence_app_service::TomlFaaSModuleConfig;
+
+use toml::value::Table;
+use toml::Value;
+
+/// Settings of a module config overridden by the `config_overrides` attribute of `marine_test`.
+#[derive(Clone, Debug, Default)]
+pub struct ModuleConfigOverrides {
+    pub envs: Vec<(String, String)>,
+    pub mapped_dirs: Vec<(String, String)>,
+    pub mounted_binaries: Vec<(String, String)>,
+    pub mem_pages_count: Option<u32>,
+    pub logger_enabled: Option<bool>,
+    pub logging_mask: Option<i32>,
+}
+
+/// Applies overrides to the config of the module with the provided name. Envs, mapped dirs
+/// and mounted binaries are added to ones from the config, other settings are replaced.
+pub fn override_module_config(
+    config: &mut TomlAppServiceConfig,
+    module_name: &str,
+    overrides: ModuleConfigOverrides,
+) {
+    let module = config
+        .toml_faas_config
+        .module
+        .iter_mut()
+        .find(|module| module.name == module_name)
+        .unwrap_or_else(|| panic!("module {} isn't found in the service config", module_name));
+
+    apply_overrides(&mut module.config, overrides);
+}
+
+fn apply_overrides(config: &mut TomlFaaSModuleConfig, overrides: ModuleConfigOverrides) {
+    let ModuleConfigOverrides {
+        envs,
+        mapped_dirs,
+        mounted_binaries,
+        mem_pages_count,
+        logger_enabled,
+        logging_mask,
+    } = overrides;
+
+    if !envs.is_empty() {
+        let wasi = config.wasi.get_or_insert_with(<_>::default);
+        extend_table(&mut wasi.envs, envs);
+    }
+
+    if !mapped_dirs.is_empty() {
+        let wasi = config.wasi.get_or_insert_with(<_>::default);
+        extend_table(&mut wasi.mapped_dirs, mapped_dirs);
+    }
+
+    if !mounted_binaries.is_empty() {
+        extend_table(&mut config.mounted_binaries, mounted_binaries);
+    }
+
+    if mem_pages_count.is_some() {
+        config.mem_pages_count = mem_pages_count;
+    }
+
+    if logger_enabled.is_some() {
+        config.logger_enabled = logger_enabled;
+    }
+
+    if logging_mask.is_some() {
+        config.logging_mask = logging_mask;
+    }
+}
+
+fn extend_table(table: &mut Option<Table>, pairs: Vec<(String, String)>) {
+    let table = table.get_or_insert_with(Table::new);
+    let pairs = pairs
+        .into_iter()
+        .map(|(key, value)| (key, Value::String(value)));
+
+    table.extend(pairs);
+}
@@ -0,0 +1,48 @@
+/*
+ * Copyright 2021 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use fluence_app_service::AppServiceError;
+
+use serde_json::Error as SerdeError;
+use thiserror::Error as ThisError;
+
+/// Errors returned by `try_`-prefixed methods generated by `marine_test`.
+#[derive(Debug, ThisError)]
+pub enum MarineTestError {
+    /// Marine failed to call a module function, e.g. the function trapped or
+    /// the provided arguments don't correspond to its signature.
+    #[error("call to Marine failed: {0}")]
+    MarineError(#[from] AppServiceError),
+
+    /// A value returned by a module function can't be converted to the output type.
+    #[error("result of a call can't be deserialized: {0}")]
+    ResultDeserializationError(#[from] SerdeError),
+}
+
+/// Errors returned by `CallParametersBuilder::build`.
+#[derive(Debug, Clone, PartialEq, Eq, ThisError)]
+pub enum CallParametersError {
+    /// Tetraplets were set for an argument the function doesn't have.
+    #[error("function doesn't have argument {argument_name}, its arguments are: {argument_names}")]
+    UnknownArgument {
+        argument_name: String,
+        argument_names: String,
+    },
+
+    /// Count of provided tetraplet vectors isn't equal to the argument count.
+    #[error("function has {expected} arguments, but tetraplets for {actual} arguments provided")]
+    TetrapletsCountMismatch { expected: usize, actual: usize },
+}
@@ -0,0 +1,55 @@
+/*
+ * Copyright 2021 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use fluence_app_service::AppServiceConfig;
+use fluence_app_service::HostImportDescriptor;
+use fluence_app_service::IType;
+use fluence_app_service::IValue;
+
+/// Creates a host import from a closure working with already lifted arguments.
+pub fn create_host_import(
+    func: impl Fn(Vec<IValue>) -> Option<IValue> + 'static,
+    argument_types: Vec<IType>,
+    output_type: Option<IType>,
+) -> HostImportDescriptor {
+    HostImportDescriptor {
+        host_exported_func: Box::new(move |_, arguments| func(arguments)),
+        argument_types,
+        output_type,
+        error_handler: None,
+    }
+}
+
+/// Sets a host import for the module with the provided name, replacing an import
+/// with the same name from the config, e.g. a mounted binary.
+pub fn set_host_import(
+    config: &mut AppServiceConfig,
+    module_name: &str,
+    import_name: &str,
+    host_import: HostImportDescriptor,
+) {
+    let module = config
+        .faas_config
+        .modules_config
+        .iter_mut()
+        .find(|module| module.import_name == module_name)
+        .unwrap_or_else(|| panic!("module {} isn't found in the service config", module_name));
+
+    module
+        .config
+        .host_imports
+        .insert(import_name.to_string(), host_import);
+}
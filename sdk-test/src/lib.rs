@@ -26,18 +26,57 @@
 )]
 #![warn(rust_2018_idioms)]
 
+mod call_parameters_builder;
+mod call_recorder;
+mod config_overrides;
+mod errors;
+mod host_imports;
+#[cfg(feature = "property_testing")]
+mod property_testing;
+mod snapshot;
+
 pub use marine_test_macro::marine_test;
 pub use marine_test_macro::fce_test;
+pub use marine_test_macro_impl::build_modules;
 
 pub use fluence_app_service::CallParameters;
 pub use fluence_app_service::SecurityTetraplet;
 
+pub use call_parameters_builder::CallParametersBuilder;
+pub use call_recorder::CallLog;
+pub use call_recorder::CallRecord;
+pub use call_recorder::CallRecorder;
+pub use errors::CallParametersError;
+pub use errors::MarineTestError;
+pub use snapshot::UPDATE_SNAPSHOTS_ENV_VAR;
+
+#[cfg(feature = "property_testing")]
+pub use property_testing::marine_proptest;
+#[cfg(feature = "property_testing")]
+pub use property_testing::marine_proptest_with_config;
+#[cfg(feature = "property_testing")]
+pub use proptest;
+
 /// These API functions are intended for internal usage in generated code.
 /// Normally, you shouldn't use them.
 pub mod internal {
     pub use fluence_app_service::AppService;
+    pub use fluence_app_service::AppServiceConfig;
     pub use fluence_app_service::TomlAppServiceConfig;
+    pub use fluence_app_service::IType;
+    pub use fluence_app_service::IValue;
+    pub use fluence_app_service::from_interface_values;
+    pub use fluence_app_service::to_interface_value;
+
+    pub use crate::call_recorder::call_module;
+    pub use crate::config_overrides::override_module_config;
+    pub use crate::config_overrides::ModuleConfigOverrides;
+    pub use crate::host_imports::create_host_import;
+    pub use crate::host_imports::set_host_import;
+    pub use crate::snapshot::assert_snapshot;
 
+    #[cfg(feature = "property_testing")]
+    pub use proptest;
     pub use serde;
     pub use serde_json;
 
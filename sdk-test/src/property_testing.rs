@@ -0,0 +1,73 @@
+/*
+ * Copyright 2021 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use proptest::strategy::Strategy;
+use proptest::test_runner::Config;
+use proptest::test_runner::TestCaseError;
+use proptest::test_runner::TestError;
+use proptest::test_runner::TestRunner;
+
+use std::cell::RefCell;
+
+/// Runs the provided test against random values generated by the strategy and panics with
+/// the minimal failing input if the test fails. Unlike the `proptest!` macro, the test could
+/// mutate captured state, so one set of generated modules and its `AppService` is reused
+/// for all cases:
+///```ignore
+/// use marine_rs_sdk_test::proptest::prelude::*;
+///
+/// #[marine_test(config_path = "/path/to/Config.toml", modules_dir = "path/to/service/modules")]
+/// fn test() {
+///     marine_rs_sdk_test::marine_proptest(any::<greeting_structs::User>(), |user| {
+///         let greeting = greeting.greeting(user.clone());
+///         prop_assert!(greeting.contains(&user.name));
+///         Ok(())
+///     });
+/// }
+///```
+pub fn marine_proptest<S: Strategy>(
+    strategy: S,
+    test: impl FnMut(S::Value) -> Result<(), TestCaseError>,
+) {
+    // failures can't be persisted near a test file, because the runner doesn't know it
+    let config = Config {
+        failure_persistence: None,
+        ..Config::default()
+    };
+
+    marine_proptest_with_config(config, strategy, test)
+}
+
+/// Does the same as `marine_proptest`, but runs tests with the provided config,
+/// f.e. to decrease count of cases for slow modules.
+pub fn marine_proptest_with_config<S: Strategy>(
+    config: Config,
+    strategy: S,
+    test: impl FnMut(S::Value) -> Result<(), TestCaseError>,
+) {
+    // TestRunner accepts only Fn, while calls of generated methods require mutable access
+    let test = RefCell::new(test);
+    let mut runner = TestRunner::new(config);
+
+    match runner.run(&strategy, |value| (test.borrow_mut())(value)) {
+        Ok(()) => {}
+        Err(TestError::Abort(reason)) => panic!("property test aborted: {}", reason),
+        Err(TestError::Fail(reason, value)) => panic!(
+            "property test failed: {}\nminimal failing input: {:#?}",
+            reason, value
+        ),
+    }
+}
@@ -0,0 +1,223 @@
+/*
+ * Copyright 2021 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Snapshot assertions: a value is serialized to pretty JSON and compared with a snapshot
+//! stored in the `snapshots` directory next to the test file. Snapshots are created or
+//! updated instead of being compared if the `MARINE_UPDATE_SNAPSHOTS` variable is set.
+
+use serde::Serialize;
+
+use std::fmt::Write;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// If this environment variable is set, snapshots are written instead of being compared.
+pub const UPDATE_SNAPSHOTS_ENV_VAR: &str = "MARINE_UPDATE_SNAPSHOTS";
+
+/// Asserts that a serializable value, f.e. a record returned by a module, is equal to
+/// the stored snapshot with the provided name:
+///```ignore
+/// let user = users.get_user("John".to_string());
+/// marine_rs_sdk_test::assert_snapshot!("john_user", user);
+///```
+/// Snapshots are stored in `snapshots/<name>.json` next to the test file, run tests with
+/// `MARINE_UPDATE_SNAPSHOTS=1` to create or update them.
+#[macro_export]
+macro_rules! assert_snapshot {
+    ($snapshot_name:expr, $value:expr) => {
+        $crate::internal::assert_snapshot(
+            $snapshot_name,
+            &$value,
+            env!("CARGO_MANIFEST_DIR"),
+            file!(),
+        )
+    };
+}
+
+/// Intended to be used only by the `assert_snapshot` macro.
+pub fn assert_snapshot<T: Serialize + ?Sized>(
+    snapshot_name: &str,
+    value: &T,
+    manifest_dir: &str,
+    test_file: &str,
+) {
+    let actual = serde_json::to_string_pretty(value)
+        .unwrap_or_else(|e| panic!("value can't be serialized to JSON: {}", e));
+
+    let snapshot_path = snapshot_path(snapshot_name, manifest_dir, test_file);
+    if std::env::var_os(UPDATE_SNAPSHOTS_ENV_VAR).is_some() {
+        update_snapshot(&snapshot_path, &actual);
+        return;
+    }
+
+    let expected = match std::fs::read_to_string(&snapshot_path) {
+        Ok(expected) => expected,
+        Err(e) => panic!(
+            "snapshot {:?} can't be read: {}, run tests with {}=1 to create it",
+            snapshot_path, e, UPDATE_SNAPSHOTS_ENV_VAR
+        ),
+    };
+
+    let expected = expected.trim_end();
+    if expected != actual {
+        panic!(
+            "value doesn't match snapshot {:?} (-snapshot +actual):\n{}\nrun tests with {}=1 to update it",
+            snapshot_path,
+            lines_diff(expected, &actual),
+            UPDATE_SNAPSHOTS_ENV_VAR
+        );
+    }
+}
+
+fn update_snapshot(snapshot_path: &Path, value: &str) {
+    if let Some(snapshots_dir) = snapshot_path.parent() {
+        std::fs::create_dir_all(snapshots_dir).unwrap_or_else(|e| {
+            panic!(
+                "snapshots directory {:?} can't be created: {}",
+                snapshots_dir, e
+            )
+        });
+    }
+
+    std::fs::write(snapshot_path, format!("{}\n", value))
+        .unwrap_or_else(|e| panic!("snapshot {:?} can't be written: {}", snapshot_path, e));
+}
+
+/// Returns a path of a snapshot next to the test file. `file!()` is relative to a workspace
+/// root, so its components are appended to the manifest directory starting after the part
+/// they have in common.
+fn snapshot_path(snapshot_name: &str, manifest_dir: &str, test_file: &str) -> PathBuf {
+    let mut test_dir = PathBuf::from(manifest_dir);
+    let mut file_path = Path::new(test_file).components();
+    let _ = file_path.next_back();
+
+    let mut truncated_file_path = Vec::new();
+    while !test_dir.ends_with(file_path.as_path()) {
+        match file_path.next_back() {
+            Some(component @ Component::Normal(_)) => truncated_file_path.push(component),
+            _ => break,
+        }
+    }
+
+    for component in truncated_file_path.iter().rev() {
+        test_dir.push(component);
+    }
+
+    test_dir
+        .join("snapshots")
+        .join(format!("{}.json", snapshot_name))
+}
+
+/// Returns a line by line diff of two texts based on their longest common subsequence.
+fn lines_diff(expected: &str, actual: &str) -> String {
+    let expected = expected.lines().collect::<Vec<_>>();
+    let actual = actual.lines().collect::<Vec<_>>();
+
+    // lcs[i][j] is a length of the longest common subsequence of expected[i..] and actual[j..]
+    let mut lcs = vec![vec![0usize; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            lcs[i][j] = if expected[i] == actual[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                std::cmp::max(lcs[i + 1][j], lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            let _ = writeln!(diff, " {}", expected[i]);
+            i += 1;
+            j += 1;
+        } else if i < expected.len() && (j == actual.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            let _ = writeln!(diff, "-{}", expected[i]);
+            i += 1;
+        } else {
+            let _ = writeln!(diff, "+{}", actual[j]);
+            j += 1;
+        }
+    }
+
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lines_diff;
+    use super::snapshot_path;
+
+    use std::path::PathBuf;
+
+    #[test]
+    fn identical_texts_diff() {
+        let text = "{\n  \"name\": \"John\"\n}";
+
+        assert_eq!(lines_diff(text, text), " {\n   \"name\": \"John\"\n }\n");
+    }
+
+    #[test]
+    fn inserted_lines_diff() {
+        let expected = "{\n  \"name\": \"John\"\n}";
+        let actual = "{\n  \"name\": \"John\",\n  \"age\": 42\n}";
+
+        let diff = lines_diff(expected, actual);
+
+        assert_eq!(
+            diff,
+            " {\n-  \"name\": \"John\"\n+  \"name\": \"John\",\n+  \"age\": 42\n }\n"
+        );
+    }
+
+    #[test]
+    fn deleted_lines_diff() {
+        let expected = "[\n  1,\n  2,\n  3\n]";
+        let actual = "[\n  1,\n  3\n]";
+
+        assert_eq!(lines_diff(expected, actual), " [\n   1,\n-  2,\n   3\n ]\n");
+    }
+
+    #[test]
+    fn snapshot_path_of_workspace_member() {
+        let path = snapshot_path("user", "/workspace/greeting", "greeting/tests/greeting.rs");
+
+        let expected_path = PathBuf::from("/workspace/greeting/tests/snapshots/user.json");
+        assert_eq!(path, expected_path);
+    }
+
+    #[test]
+    fn snapshot_path_of_standalone_crate() {
+        let path = snapshot_path("user", "/greeting", "tests/greeting.rs");
+
+        let expected_path = PathBuf::from("/greeting/tests/snapshots/user.json");
+        assert_eq!(path, expected_path);
+    }
+
+    #[test]
+    fn snapshot_path_of_nested_workspace_member() {
+        let path = snapshot_path(
+            "user",
+            "/workspace/services/greeting",
+            "services/greeting/src/lib.rs",
+        );
+
+        let expected_path = PathBuf::from("/workspace/services/greeting/src/snapshots/user.json");
+        assert_eq!(path, expected_path);
+    }
+}
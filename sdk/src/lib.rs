@@ -68,7 +68,11 @@
 #![warn(rust_2018_idioms)]
 
 mod call_parameters;
+#[cfg(feature = "memory_stats")]
+mod memory_stats;
 mod mounted_binary;
+#[cfg(feature = "panic_hook")]
+mod panic_report;
 
 #[allow(unused_extern_crates)]
 // sdk is used inside CallParameters and MountedBinaryResult glue code
@@ -76,6 +80,7 @@ extern crate self as marine_rs_sdk;
 
 pub use marine_macro::marine;
 pub use marine_macro::fce;
+pub use marine_import_macro::marine_import;
 
 pub use call_parameters::CallParameters;
 pub use call_parameters::SecurityTetraplet;
@@ -86,6 +91,25 @@ pub use marine_rs_sdk_main::WasmLoggerBuilder;
 #[cfg(feature = "logger")]
 pub use marine_rs_sdk_main::TargetMap;
 
+#[cfg(feature = "arena")]
+pub use marine_rs_sdk_main::call_arena_stats;
+#[cfg(feature = "arena")]
+pub use marine_rs_sdk_main::CallArenaCounters;
+#[cfg(feature = "arena")]
+pub use marine_rs_sdk_main::CallArenaStats;
+
+#[cfg(feature = "memory_stats")]
+pub use memory_stats::MemoryStats;
+#[cfg(feature = "memory_stats")]
+pub use memory_stats::sdk_memory_stats;
+
+#[cfg(feature = "panic_hook")]
+pub use marine_rs_sdk_main::install_panic_hook;
+#[cfg(feature = "panic_hook")]
+pub use panic_report::PanicReport;
+#[cfg(feature = "panic_hook")]
+pub use panic_report::sdk_take_panic_report;
+
 pub use mounted_binary::MountedBinaryResult;
 pub use mounted_binary::MountedBinaryStringResult;
 pub use mounted_binary::SUCCESS_CODE as BINARY_SUCCESS_CODE;
@@ -100,5 +124,9 @@ pub mod internal {
     pub use marine_rs_sdk_main::set_result_ptr;
     pub use marine_rs_sdk_main::set_result_size;
     pub use marine_rs_sdk_main::add_object_to_release;
+    pub use marine_rs_sdk_main::retain_buffer;
+    pub use marine_rs_sdk_main::reset_call_arena;
+    pub use marine_rs_sdk_main::take_allocated_vec;
+    pub use marine_rs_sdk_main::AllocElemType;
     pub use marine_timestamp_macro::build_timestamp;
 }
@@ -0,0 +1,60 @@
+/*
+ * Copyright 2021 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use marine_macro::marine;
+
+use serde::Serialize;
+use serde::Deserialize;
+
+/// Describes memory retained by a module between calls.
+#[marine]
+#[derive(Clone, PartialEq, Default, Eq, Debug, Serialize, Deserialize)]
+pub struct MemoryStats {
+    /// Bytes allocated by a host with `allocate` and not freed by `deallocate`.
+    pub allocated_bytes: u64,
+
+    /// Count of objects kept alive until a host calls `release_objects`.
+    pub objects_to_release: u64,
+
+    /// Approximate size in bytes of objects kept alive until a host calls `release_objects`,
+    /// heap buffers are counted only for strings and vectors of scalar types.
+    pub objects_to_release_bytes: u64,
+
+    /// Bytes owned by the call arena enabled by the `arena` feature.
+    pub arena_bytes: u64,
+
+    /// The maximum observed value of `allocated_bytes + objects_to_release_bytes + arena_bytes`.
+    pub peak_bytes: u64,
+}
+
+/// Returns memory usage counters of this module. It's also exported from a module
+/// to let a host monitor memory retained by a service.
+#[marine]
+pub fn sdk_memory_stats() -> MemoryStats {
+    marine_rs_sdk_main::memory_stats().into()
+}
+
+impl From<marine_rs_sdk_main::MemoryStats> for MemoryStats {
+    fn from(stats: marine_rs_sdk_main::MemoryStats) -> Self {
+        Self {
+            allocated_bytes: stats.allocated_bytes as _,
+            objects_to_release: stats.objects_to_release as _,
+            objects_to_release_bytes: stats.objects_to_release_bytes as _,
+            arena_bytes: stats.arena_bytes as _,
+            peak_bytes: stats.peak_bytes as _,
+        }
+    }
+}
@@ -0,0 +1,57 @@
+/*
+ * Copyright 2021 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use marine_macro::marine;
+
+use serde::Serialize;
+use serde::Deserialize;
+
+/// Describes a panic occurred in a module.
+#[marine]
+#[derive(Clone, PartialEq, Default, Eq, Debug, Serialize, Deserialize)]
+pub struct PanicReport {
+    /// Panic message or an empty string if a panic payload isn't a string.
+    pub message: String,
+
+    /// Source file where the panic occurred.
+    pub file: String,
+
+    pub line: u32,
+
+    pub column: u32,
+}
+
+/// Returns the last panic occurred in a module and clears it. It's exported from a module
+/// to let a host get a reason of a trap caused by a panic. The result contains at most one
+/// element, an empty array means that there were no panics since the previous call.
+#[marine]
+pub fn sdk_take_panic_report() -> Vec<PanicReport> {
+    marine_rs_sdk_main::take_panic_report()
+        .map(Into::into)
+        .into_iter()
+        .collect()
+}
+
+impl From<marine_rs_sdk_main::PanicReport> for PanicReport {
+    fn from(report: marine_rs_sdk_main::PanicReport) -> Self {
+        Self {
+            message: report.message,
+            file: report.file,
+            line: report.line,
+            column: report.column,
+        }
+    }
+}